@@ -0,0 +1,92 @@
+use sentence_splitter::helper::{bundle, cache};
+use sentence_splitter::helper::common::Language;
+use sentence_splitter::helper::errors::{LanguageParseError, SentenceSplitterError};
+
+use std::env;
+use std::path::Path;
+use std::process;
+
+
+const USAGE: &str = "\
+Usage: sentence-splitter cache <command>
+       sentence-splitter bundle <command>
+
+Cache commands:
+    list                     List the cached weight files
+    verify                   Check the cached weight files against their SHA-256 digests
+    prefetch [LANGUAGE...]   Download the weights of LANGUAGE, or of all languages
+    remove LANGUAGE          Remove the cached weights of LANGUAGE
+    clear                    Remove every cached file
+    dir                      Print the cache directory
+
+Bundle commands:
+    export FILE LANGUAGE...  Write the weights of LANGUAGE into the archive FILE
+    import FILE              Copy the weights of the archive FILE into the cache";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["cache", "list"] => list(),
+        ["cache", "verify"] => verify(),
+        ["cache", "prefetch"] => cache::prefetch_all().map(|paths| println!("{} files cached", paths.len())),
+        ["cache", "prefetch", languages @ ..] => parse_languages(languages)
+            .and_then(|languages| cache::prefetch(&languages))
+            .map(|paths| println!("{} files cached", paths.len())),
+        ["cache", "remove", language] => parse_languages(&[language])
+            .and_then(|languages| cache::remove_cached(languages[0]))
+            .map(|removed| if !removed { println!("{} is not cached", language) }),
+        ["cache", "clear"] => cache::clear_cache(),
+        ["cache", "dir"] => {
+            println!("{}", cache::cache_directory().display());
+            Ok(())
+        }
+        ["bundle", "export", file, languages @ ..] if !languages.is_empty() => parse_languages(languages)
+            .and_then(|languages| bundle::export_bundle(&languages, Path::new(file)))
+            .map(|manifest| println!("{} languages exported", manifest.entries.len())),
+        ["bundle", "import", file] => bundle::import_bundle(Path::new(file))
+            .map(|paths| println!("{} files cached", paths.len())),
+        ["help"] | ["--help"] | ["-h"] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_languages(names: &[&str]) -> Result<Vec<Language>, SentenceSplitterError> {
+    names.iter()
+        .map(|name| name.parse().map_err(|error: LanguageParseError| {
+            SentenceSplitterError::ConfigError(error.to_string())
+        }))
+        .collect()
+}
+
+fn list() -> Result<(), SentenceSplitterError> {
+    for entry in cache::list_cached()? {
+        let language = entry.language.map_or_else(|| String::from("-"), |language| language.to_string());
+        println!("{}\t{}\t{}\t{}", language, entry.size, entry.sha256, entry.url);
+    }
+    Ok(())
+}
+
+fn verify() -> Result<(), SentenceSplitterError> {
+    let damaged = cache::verify_cached()?;
+    for entry in &damaged {
+        println!("{}\t{}", entry.sha256, entry.path.display());
+    }
+    if !damaged.is_empty() {
+        return Err(SentenceSplitterError::ChecksumError(format!("{} cached files do not match their digest", damaged.len())));
+    }
+    println!("all cached files are valid");
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::resources::{sha256_bytes, Resource};
+#[cfg(feature = "remote")]
+use crate::helper::resources::{store_in_cache, weights_url};
+use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+#[cfg(feature = "remote")]
+use std::path::PathBuf;
+
+
+static MANIFEST_PATH: &str = "manifest.json";
+
+/// Bundle format version written by `export_bundle`.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Table of contents of a bundle, stored as `manifest.json` next to the
+/// weight files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub version: u32,
+    pub entries: Vec<BundleEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// Language name, e.g. `"English"`.
+    pub language: String,
+    /// Path of the weight file inside the archive.
+    pub file: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Writes the weights of `languages` into a `.tar.gz` archive at `path`,
+/// downloading the ones that are not cached yet.
+pub fn export_bundle(languages: &[Language], path: &Path) -> Result<BundleManifest, SentenceSplitterError> {
+    let mut files: Vec<(BundleEntry, Vec<u8>)> = Vec::new();
+    for language in languages {
+        let weights = PunktSentenceTokenizer::_language_weights(*language)?.read()?.into_owned();
+        let entry = BundleEntry {
+            language: language.to_string(),
+            file: format!("weights/{}.json", language),
+            size: weights.len() as u64,
+            sha256: sha256_bytes(&weights),
+        };
+        files.push((entry, weights));
+    }
+    let manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        entries: files.iter().map(|(entry, _)| entry.clone()).collect(),
+    };
+
+    let mut archive = tar::Builder::new(GzEncoder::new(File::create(path)?, Compression::default()));
+    _append(&mut archive, MANIFEST_PATH, &serde_json::to_vec_pretty(&manifest)?)?;
+    for (entry, weights) in &files {
+        _append(&mut archive, &entry.file, weights)?;
+    }
+    archive.into_inner()?.finish()?;
+
+    Ok(manifest)
+}
+
+/// Copies the weights of a bundle into the cache, where they are used
+/// instead of downloading them, online and offline. Returns the cached paths.
+#[cfg(feature = "remote")]
+pub fn import_bundle(path: &Path) -> Result<Vec<PathBuf>, SentenceSplitterError> {
+    Bundle::open(path)?.import()
+}
+
+/// The content of a bundle archive, with every weight file verified
+/// against the manifest.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    manifest: BundleManifest,
+    weights: HashMap<Language, Vec<u8>>,
+}
+
+impl Bundle {
+
+    pub fn open(path: &Path) -> Result<Bundle, SentenceSplitterError> {
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(path)?));
+        let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+        for file in archive.entries()? {
+            let mut file = file?;
+            let name = file.path()?.to_string_lossy().into_owned();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            files.insert(name, content);
+        }
+
+        let manifest: BundleManifest = match files.get(MANIFEST_PATH) {
+            Some(manifest) => serde_json::from_slice(manifest)?,
+            None => return Err(SentenceSplitterError::BundleError(format!("{} has no {}", path.display(), MANIFEST_PATH)))
+        };
+        if manifest.version > BUNDLE_VERSION {
+            return Err(SentenceSplitterError::BundleError(format!("unsupported bundle version {}", manifest.version)));
+        }
+
+        let mut weights = HashMap::new();
+        for entry in &manifest.entries {
+            let language: Language = entry.language.parse()
+                .map_err(|error: crate::helper::errors::LanguageParseError| SentenceSplitterError::BundleError(error.to_string()))?;
+            let content = files.remove(&entry.file)
+                .ok_or_else(|| SentenceSplitterError::BundleError(format!("{} is missing", entry.file)))?;
+            if content.len() as u64 != entry.size {
+                return Err(SentenceSplitterError::BundleError(
+                    format!("{}: expected {} bytes, got {}", entry.file, entry.size, content.len())
+                ));
+            }
+            let sha256 = sha256_bytes(&content);
+            if sha256 != entry.sha256 {
+                return Err(SentenceSplitterError::ChecksumError(
+                    format!("{}: expected SHA-256 {}, got {}", entry.file, entry.sha256, sha256)
+                ));
+            }
+            weights.insert(language, content);
+        }
+
+        Ok(Bundle { manifest, weights })
+    }
+
+    pub fn manifest(&self) -> &BundleManifest {
+        &self.manifest
+    }
+
+    pub fn languages(&self) -> Vec<Language> {
+        self.manifest.entries.iter().filter_map(|entry| entry.language.parse().ok()).collect()
+    }
+
+    /// A tokenizer for `language` using the weights of the bundle.
+    pub fn tokenizer(&self, language: Language) -> Result<PunktSentenceTokenizer, SentenceSplitterError> {
+        let weights = self.weights.get(&language)
+            .ok_or_else(|| SentenceSplitterError::BundleError(format!("the bundle has no weights for {}", language)))?;
+        PunktSentenceTokenizer::from_resource(&Resource::Bytes(weights.clone()), Some(language))
+    }
+
+    /// Stores the weights in the cache under the current weight URLs.
+    #[cfg(feature = "remote")]
+    pub fn import(&self) -> Result<Vec<PathBuf>, SentenceSplitterError> {
+        self.languages().into_iter()
+            .map(|language| store_in_cache(&weights_url(language), &format!("punkt/{}", language), &self.weights[&language]))
+            .collect()
+    }
+}
+
+fn _append<W: std::io::Write>(archive: &mut tar::Builder<W>, path: &str, content: &[u8]) -> Result<(), SentenceSplitterError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, path, content)?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod bundle_tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    pub(super) fn bundle_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("sentence-splitter-{}-{}.tar.gz", std::process::id(), name))
+    }
+
+    fn write_bundle(name: &str, entry: BundleEntry, content: &[u8]) -> PathBuf {
+        let path = bundle_path(name);
+        let manifest = BundleManifest { version: BUNDLE_VERSION, entries: vec![entry] };
+        let mut archive = tar::Builder::new(GzEncoder::new(File::create(&path).unwrap(), Compression::default()));
+        _append(&mut archive, MANIFEST_PATH, &serde_json::to_vec(&manifest).unwrap()).unwrap();
+        _append(&mut archive, "weights/English.json", content).unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_damaged_bundle() {
+        let content = b"{\"abbrev_types\": []}";
+        let path = write_bundle("damaged", BundleEntry {
+            language: String::from("English"),
+            file: String::from("weights/English.json"),
+            size: content.len() as u64,
+            sha256: sha256_bytes(b"{}"),
+        }, content);
+
+        assert!(matches!(Bundle::open(&path), Err(SentenceSplitterError::ChecksumError(_))));
+    }
+
+    #[test]
+    fn test_wrong_size() {
+        let content = b"{}";
+        let path = write_bundle("size", BundleEntry {
+            language: String::from("English"),
+            file: String::from("weights/English.json"),
+            size: 3,
+            sha256: sha256_bytes(content),
+        }, content);
+
+        assert!(matches!(Bundle::open(&path), Err(SentenceSplitterError::BundleError(_))));
+    }
+}
+
+#[cfg(all(test, feature = "remote"))]
+mod remote_bundle_tests {
+    use super::*;
+    use crate::helper::cache::clear_cache;
+    use crate::helper::resources::{set_base_url, set_offline};
+    use crate::helper::test_server::{remote_test_guard, TestServer};
+    use super::bundle_tests::bundle_path;
+    use std::fs;
+
+    fn export(name: &str) -> PathBuf {
+        let server = TestServer::serve(vec![
+            ("/English.json", fs::read("data/weights/English.json").unwrap()),
+            ("/German.json", fs::read("data/weights/German.json").unwrap()),
+        ]);
+        set_base_url(&server.url);
+        let path = bundle_path(name);
+        export_bundle(&[Language::English, Language::German], &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_tokenizer_from_bundle() {
+        let _guard = remote_test_guard();
+        let bundle = Bundle::open(&export("tokenizer")).unwrap();
+
+        assert_eq!(bundle.manifest().version, BUNDLE_VERSION);
+        assert_eq!(bundle.languages(), vec![Language::English, Language::German]);
+        assert_eq!(bundle.manifest().entries[1].file, "weights/German.json");
+        assert_eq!(
+            bundle.tokenizer(Language::German).unwrap().tokenize("Er kam am 3. Mai. Sie blieb.", true),
+            vec!["Er kam am 3. Mai.", "Sie blieb."]
+        );
+        assert!(matches!(bundle.tokenizer(Language::French), Err(SentenceSplitterError::BundleError(_))));
+    }
+
+    #[test]
+    fn test_import_for_offline_use() {
+        let _guard = remote_test_guard();
+        let path = export("import");
+        clear_cache().unwrap();
+
+        set_base_url("http://unreachable.invalid");
+        let imported = import_bundle(&path);
+        set_offline(true);
+        let cached = PunktSentenceTokenizer::_language_weights(Language::English).unwrap().get_local_path();
+        set_offline(false);
+
+        assert_eq!(imported.unwrap().len(), 2);
+        assert_eq!(fs::read(cached.unwrap()).unwrap(), fs::read("data/weights/English.json").unwrap());
+    }
+
+    #[test]
+    fn test_imports_are_not_downloaded_again() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![
+            ("/English.json", fs::read("data/weights/English.json").unwrap()),
+        ]);
+        set_base_url(&server.url);
+        let path = bundle_path("online");
+        export_bundle(&[Language::English], &path).unwrap();
+        clear_cache().unwrap();
+
+        let imported = import_bundle(&path).unwrap();
+        let downloads = server.downloads();
+        let cached = PunktSentenceTokenizer::_language_weights(Language::English).unwrap().get_local_path().unwrap();
+
+        assert_eq!(server.downloads(), downloads);
+        assert_eq!(cached, imported[0]);
+        assert_eq!(fs::read(cached).unwrap(), fs::read("data/weights/English.json").unwrap());
+    }
+}
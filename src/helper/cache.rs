@@ -0,0 +1,238 @@
+use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::resources::{remove_from_cache, sha256_file, weights_sha256, CACHE};
+use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+
+/// A downloaded file in the weight cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// Language of the cache subdirectory, `None` for other resources.
+    pub language: Option<Language>,
+    pub url: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl CacheEntry {
+    /// Whether the file matches the published digest of its language,
+    /// `None` when there is no digest to compare with.
+    pub fn is_verified(&self) -> Option<bool> {
+        let expected = weights_sha256(self.language?)?;
+        Some(self.sha256 == expected)
+    }
+}
+
+/// Metadata written next to every download and bundle import.
+#[derive(Deserialize)]
+struct CacheMeta {
+    resource: String,
+    resource_path: PathBuf,
+}
+
+/// Root of the weight cache, `~/.cache/.sentence-splitter` unless
+/// `SENTENCE_SPLITTER_CACHE` is set.
+pub fn cache_directory() -> PathBuf {
+    CACHE.dir.clone()
+}
+
+/// All cached files, sorted by path.
+pub fn list_cached() -> Result<Vec<CacheEntry>, SentenceSplitterError> {
+    let mut entries = Vec::new();
+    for meta in _cached_files(&cache_directory())? {
+        let language = meta.resource_path.parent()
+            .and_then(|directory| directory.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse().ok());
+
+        entries.push(CacheEntry {
+            language,
+            url: meta.resource,
+            size: fs::metadata(&meta.resource_path)?.len(),
+            sha256: sha256_file(&meta.resource_path)?,
+            path: meta.resource_path,
+        });
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Cached files that do not match the published digest of their language.
+pub fn verify_cached() -> Result<Vec<CacheEntry>, SentenceSplitterError> {
+    Ok(list_cached()?.into_iter().filter(|entry| entry.is_verified() == Some(false)).collect())
+}
+
+/// Downloads the weights of `languages` unless they are cached already.
+pub fn prefetch(languages: &[Language]) -> Result<Vec<PathBuf>, SentenceSplitterError> {
+    languages.iter()
+        .map(|language| PunktSentenceTokenizer::_language_weights(*language)?.get_local_path())
+        .collect()
+}
+
+/// Downloads the weights of every built-in language that has published
+/// weights.
+pub fn prefetch_all() -> Result<Vec<PathBuf>, SentenceSplitterError> {
+    let languages: Vec<Language> = Language::iter()
+        .filter(|language| weights_sha256(*language).is_some())
+        .collect();
+    prefetch(&languages)
+}
+
+/// Removes the cached weights of `language`, waiting for processes that
+/// are downloading or reading them. Returns false when none were cached.
+pub fn remove_cached(language: Language) -> Result<bool, SentenceSplitterError> {
+    _remove_all(&cache_directory().join("punkt").join(language.to_string()))
+}
+
+/// Removes every cached file. Lock files are kept, other processes may be
+/// waiting on them.
+pub fn clear_cache() -> Result<(), SentenceSplitterError> {
+    _remove_all(&cache_directory())?;
+    Ok(())
+}
+
+fn _remove_all(directory: &Path) -> Result<bool, SentenceSplitterError> {
+    let root = cache_directory();
+    let cached = _cached_files(directory)?;
+    for meta in &cached {
+        let cache_subdir = meta.resource_path.parent()
+            .and_then(|parent| parent.strip_prefix(&root).ok())
+            .and_then(|subdir| subdir.to_str())
+            .ok_or_else(|| SentenceSplitterError::IOError(format!("{} is outside of the cache", meta.resource_path.display())))?;
+        remove_from_cache(&meta.resource, cache_subdir, &meta.resource_path)?;
+    }
+    Ok(!cached.is_empty())
+}
+
+/// Metadata of the complete downloads below `directory`.
+fn _cached_files(directory: &Path) -> Result<Vec<CacheMeta>, SentenceSplitterError> {
+    let mut meta_paths = Vec::new();
+    _find_meta_files(directory, &mut meta_paths)?;
+
+    let mut cached = Vec::new();
+    for meta_path in meta_paths {
+        let meta: CacheMeta = match serde_json::from_str(&fs::read_to_string(&meta_path)?) {
+            Ok(meta) => meta,
+            // not written by the cache
+            Err(_) => continue
+        };
+        if meta.resource_path.is_file() {
+            cached.push(meta);
+        }
+    }
+    Ok(cached)
+}
+
+fn _find_meta_files(directory: &Path, meta_paths: &mut Vec<PathBuf>) -> Result<(), SentenceSplitterError> {
+    if !directory.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            _find_meta_files(&path, meta_paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "meta") {
+            meta_paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+    use crate::helper::resources::{sha256_bytes, set_base_url, RemoteResource, Resource};
+    use crate::helper::test_server::{remote_test_guard, TestServer};
+    use fs2::FileExt;
+    use std::thread;
+    use std::time::Duration;
+
+    fn mirror() -> TestServer {
+        let server = TestServer::serve(vec![
+            ("/English.json", fs::read("data/weights/English.json").unwrap()),
+            ("/German.json", fs::read("data/weights/German.json").unwrap()),
+        ]);
+        set_base_url(&server.url);
+        server
+    }
+
+    #[test]
+    fn test_prefetch_and_list() {
+        let _guard = remote_test_guard();
+        clear_cache().unwrap();
+        let server = mirror();
+
+        prefetch(&[Language::English, Language::German]).unwrap();
+        prefetch(&[Language::English]).unwrap();
+        let entries = list_cached().unwrap();
+
+        assert_eq!(server.downloads(), 2);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].language, Some(Language::English));
+        assert_eq!(entries[0].url, format!("{}/English.json", server.url));
+        assert_eq!(entries[0].size, fs::metadata("data/weights/English.json").unwrap().len());
+        assert_eq!(entries[0].is_verified(), Some(true));
+        assert_eq!(entries[1].language, Some(Language::German));
+        assert!(verify_cached().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verify_reports_damaged_files() {
+        let _guard = remote_test_guard();
+        clear_cache().unwrap();
+        let _server = mirror();
+
+        let path = prefetch(&[Language::German]).unwrap().remove(0);
+        fs::write(&path, "{}").unwrap();
+        let damaged = verify_cached().unwrap();
+
+        assert_eq!(damaged.len(), 1);
+        assert_eq!(damaged[0].path, path);
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let _guard = remote_test_guard();
+        clear_cache().unwrap();
+        let server = mirror();
+        prefetch(&[Language::English, Language::German]).unwrap();
+        Resource::Remote(RemoteResource::new(&format!("{}/English.json", server.url), "other")).get_local_path().unwrap();
+
+        assert!(remove_cached(Language::German).unwrap());
+        assert!(!remove_cached(Language::German).unwrap());
+        let entries = list_cached().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().any(|entry| entry.language.is_none()));
+
+        clear_cache().unwrap();
+        assert!(list_cached().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_waits_for_the_lock() {
+        let _guard = remote_test_guard();
+        clear_cache().unwrap();
+        let server = mirror();
+        let path = prefetch(&[Language::German]).unwrap().remove(0);
+        let lock_path = cache_directory().join("punkt/German")
+            .join(format!("{}.lock", sha256_bytes(format!("{}/German.json", server.url).as_bytes())));
+
+        // another process reading the weights
+        let lock = fs::File::open(&lock_path).unwrap();
+        lock.lock_exclusive().unwrap();
+        let removal = thread::spawn(|| remove_cached(Language::German).unwrap());
+        thread::sleep(Duration::from_millis(200));
+        assert!(path.is_file());
+
+        lock.unlock().unwrap();
+        assert!(removal.join().unwrap());
+        assert!(!path.exists());
+        assert!(lock_path.is_file());
+    }
+}
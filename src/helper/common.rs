@@ -1,3 +1,6 @@
+use crate::helper::errors::LanguageParseError;
+use crate::helper::languages::{custom_languages, get_custom_language};
+
 use std::str::FromStr;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 
@@ -20,39 +23,101 @@ pub enum Language {
     Slovenian,
     Spanish,
     Swedish,
-    Turkish
+    Turkish,
+    /// A language added with `helper::languages::register_language`.
+    Custom(CustomLanguageId)
+}
+
+/// Names a registered language. Only `register_language` creates one, so a
+/// `Language::Custom` was registered, though it may have been unregistered
+/// since.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CustomLanguageId(&'static str);
+
+impl CustomLanguageId {
+    pub(crate) fn new(name: &'static str) -> Self {
+        CustomLanguageId(name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
 }
 
 impl FromStr for Language {
 
-    type Err = ();
+    type Err = LanguageParseError;
 
+    /// Accepts names in any case, common aliases such as `"slovene"`,
+    /// ISO 639-1/639-2/639-3 codes and BCP 47 tags such as `"pt-BR"`, whose
+    /// primary subtag decides the language. Built-in languages take
+    /// precedence over registered ones.
     fn from_str(input: &str) -> Result<Language, Self::Err> {
-        match input {
-            "Czech"      => Ok(Language::Czech),
-            "Danish"     => Ok(Language::Danish),
-            "Dutch"      => Ok(Language::Dutch),
-            "English"    => Ok(Language::English),
-            "Estonian"   => Ok(Language::Estonian),
-            "Finnish"    => Ok(Language::Finnish),
-            "French"     => Ok(Language::French),
-            "German"     => Ok(Language::German),
-            "Greek"      => Ok(Language::Greek),
-            "Italian"    => Ok(Language::Italian),
-            "Norwegian"  => Ok(Language::Norwegian),
-            "Polish"     => Ok(Language::Polish),
-            "Portuguese" => Ok(Language::Portuguese),
-            "Slovenian"  => Ok(Language::Slovenian),
-            "Spanish"    => Ok(Language::Spanish),
-            "Swedish"    => Ok(Language::Swedish),
-            "Turkish"    => Ok(Language::Turkish),
-            _ => Err(())
-        }
+        let input = input.trim();
+        let primary_subtag = input.split(['-', '_']).next().unwrap_or(input);
+
+        _find_language(input)
+            .or_else(|| _find_language(primary_subtag))
+            .ok_or_else(|| LanguageParseError {
+                input: input.to_string(),
+                supported: Language::iter().map(|language| language.to_string()).collect(),
+            })
+    }
+}
+
+fn _find_language(input: &str) -> Option<Language> {
+    let input = input.to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some((_, language)) = LANGUAGE_ALIASES.iter().find(|(alias, _)| *alias == input) {
+        return Some(*language);
     }
+    Language::iter().find(|language| {
+        language.to_string().to_lowercase() == input || language.codes().contains(&input)
+    })
 }
 
+static BUILTIN_LANGUAGES: [Language; 17] = [
+    Language::Czech,
+    Language::Danish,
+    Language::Dutch,
+    Language::English,
+    Language::Estonian,
+    Language::Finnish,
+    Language::French,
+    Language::German,
+    Language::Greek,
+    Language::Italian,
+    Language::Norwegian,
+    Language::Polish,
+    Language::Portuguese,
+    Language::Slovenian,
+    Language::Spanish,
+    Language::Swedish,
+    Language::Turkish,
+];
+
+static LANGUAGE_ALIASES: &[(&str, Language)] = &[
+    ("slovene", Language::Slovenian),
+    ("castilian", Language::Spanish),
+    ("flemish", Language::Dutch),
+    ("modern greek", Language::Greek),
+    ("bokmål", Language::Norwegian),
+    ("bokmal", Language::Norwegian),
+    ("nynorsk", Language::Norwegian),
+    ("nb", Language::Norwegian),
+    ("nob", Language::Norwegian),
+    ("nn", Language::Norwegian),
+    ("nno", Language::Norwegian),
+    ("ekk", Language::Estonian),
+];
+
 impl Display for Language {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let Language::Custom(id) = self {
+            return write!(f, "{}", id.name());
+        }
         write!(f, "{}", {
             let input_string = format!("{:?}", self);
             let mut output: Vec<&str> = Vec::new();
@@ -74,26 +139,147 @@ impl Display for Language {
     }
 }
 
+/// Serialized as the language name; any form accepted by `from_str` is
+/// deserialized.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        input.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The codes of a language in each ISO 639 part.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct LanguageCodes {
+    pub iso_639_1: &'static str,
+    /// Terminology code, e.g. `deu`.
+    pub iso_639_2t: &'static str,
+    /// Bibliographic code, e.g. `ger`.
+    pub iso_639_2b: &'static str,
+    pub iso_639_3: &'static str,
+}
+
+impl LanguageCodes {
+    const fn new(iso_639_1: &'static str, iso_639_2t: &'static str, iso_639_2b: &'static str) -> Self {
+        LanguageCodes { iso_639_1, iso_639_2t, iso_639_2b, iso_639_3: iso_639_2t }
+    }
+
+    pub fn contains(&self, code: &str) -> bool {
+        [self.iso_639_1, self.iso_639_2t, self.iso_639_2b, self.iso_639_3].contains(&code)
+    }
+}
+
 impl Language {
-    pub fn get_iso_639_1_code(&self) -> &'static str {
+
+    /// Built-in languages in alphabetical order, followed by the registered ones.
+    pub fn iter() -> impl Iterator<Item = Language> {
+        BUILTIN_LANGUAGES.iter().copied().chain(custom_languages())
+    }
+
+    /// A registered language reports its registered code in every form.
+    pub fn codes(&self) -> LanguageCodes {
         match self {
-            Language::Czech => "cs",
-            Language::Danish => "da",
-            Language::Dutch => "nl",
-            Language::English => "en",
-            Language::Estonian => "et",
-            Language::Finnish => "fi",
-            Language::French => "fr",
-            Language::German => "de",
-            Language::Greek => "el",
-            Language::Italian => "it",
-            Language::Norwegian => "no",
-            Language::Polish => "pl",
-            Language::Portuguese => "pt",
-            Language::Slovenian => "sl",
-            Language::Spanish => "es",
-            Language::Swedish => "sv",
-            Language::Turkish => "tr"
+            Language::Czech => LanguageCodes::new("cs", "ces", "cze"),
+            Language::Danish => LanguageCodes::new("da", "dan", "dan"),
+            Language::Dutch => LanguageCodes::new("nl", "nld", "dut"),
+            Language::English => LanguageCodes::new("en", "eng", "eng"),
+            Language::Estonian => LanguageCodes::new("et", "est", "est"),
+            Language::Finnish => LanguageCodes::new("fi", "fin", "fin"),
+            Language::French => LanguageCodes::new("fr", "fra", "fre"),
+            Language::German => LanguageCodes::new("de", "deu", "ger"),
+            Language::Greek => LanguageCodes::new("el", "ell", "gre"),
+            Language::Italian => LanguageCodes::new("it", "ita", "ita"),
+            Language::Norwegian => LanguageCodes::new("no", "nor", "nor"),
+            Language::Polish => LanguageCodes::new("pl", "pol", "pol"),
+            Language::Portuguese => LanguageCodes::new("pt", "por", "por"),
+            Language::Slovenian => LanguageCodes::new("sl", "slv", "slv"),
+            Language::Spanish => LanguageCodes::new("es", "spa", "spa"),
+            Language::Swedish => LanguageCodes::new("sv", "swe", "swe"),
+            Language::Turkish => LanguageCodes::new("tr", "tur", "tur"),
+            Language::Custom(_) => {
+                let code = get_custom_language(self).map_or("", |language| language.iso_code);
+                LanguageCodes { iso_639_1: code, iso_639_2t: code, iso_639_2b: code, iso_639_3: code }
+            }
+        }
+    }
+
+    pub fn get_iso_639_1_code(&self) -> &'static str {
+        self.codes().iso_639_1
+    }
+
+    pub fn get_iso_639_3_code(&self) -> &'static str {
+        self.codes().iso_639_3
+    }
+}
+
+
+#[cfg(test)]
+mod language_parsing_tests {
+    use super::*;
+
+    #[test]
+    fn test_names_in_any_case() {
+        assert_eq!("English".parse(), Ok(Language::English));
+        assert_eq!("english".parse(), Ok(Language::English));
+        assert_eq!(" PORTUGUESE ".parse(), Ok(Language::Portuguese));
+    }
+
+    #[test]
+    fn test_aliases() {
+        assert_eq!("slovene".parse(), Ok(Language::Slovenian));
+        assert_eq!("Bokmål".parse(), Ok(Language::Norwegian));
+    }
+
+    #[test]
+    fn test_iso_codes() {
+        assert_eq!("de".parse(), Ok(Language::German));
+        assert_eq!("deu".parse(), Ok(Language::German));
+        assert_eq!("ger".parse(), Ok(Language::German));
+        assert_eq!("EL".parse(), Ok(Language::Greek));
+    }
+
+    #[test]
+    fn test_bcp_47_tags() {
+        assert_eq!("pt-BR".parse(), Ok(Language::Portuguese));
+        assert_eq!("en_US".parse(), Ok(Language::English));
+        assert_eq!("sl-Latn-SI".parse(), Ok(Language::Slovenian));
+        assert_eq!("nb-NO".parse(), Ok(Language::Norwegian));
+    }
+
+    #[test]
+    fn test_parse_error_lists_supported_languages() {
+        let error = "Klingon".parse::<Language>().unwrap_err();
+
+        assert_eq!(error.input, "Klingon");
+        assert!(error.supported.contains(&String::from("Turkish")));
+        assert!(error.to_string().starts_with("Unknown language `Klingon`, supported languages: Czech, Danish"));
+        assert!("".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for language in BUILTIN_LANGUAGES.iter() {
+            let codes = language.codes();
+            assert_eq!(codes.iso_639_1.parse(), Ok(*language));
+            assert_eq!(codes.iso_639_2t.parse(), Ok(*language));
+            assert_eq!(codes.iso_639_2b.parse(), Ok(*language));
+            assert_eq!(language.to_string().parse(), Ok(*language));
         }
+        assert_eq!(Language::French.codes().iso_639_2b, "fre");
+        assert_eq!(Language::French.get_iso_639_3_code(), "fra");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_iter_starts_with_builtin_languages() {
+        let languages: Vec<Language> = Language::iter().take(17).collect();
+        assert_eq!(languages, BUILTIN_LANGUAGES.to_vec());
+    }
+}
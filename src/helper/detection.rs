@@ -0,0 +1,234 @@
+use crate::helper::common::Language;
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+
+/// Ranked character n-grams of each language, built from the vocabulary of
+/// its weight file, and its most frequent function words. Generated by
+/// `utils/build-language-profiles.py`.
+static PROFILES_JSON: &str = include_str!("../../data/language-profiles.json");
+
+const PROFILE_SIZE: usize = 1000;
+
+/// Distance removed for every function word of the language in the text.
+const WORD_BONUS: f64 = PROFILE_SIZE as f64 / 4.0;
+
+#[derive(Deserialize)]
+struct RawProfile {
+    ngrams: Vec<String>,
+    words: Vec<String>,
+}
+
+struct Profile {
+    ngrams: HashMap<String, usize>,
+    words: HashSet<String>,
+}
+
+lazy_static! {
+    static ref PROFILES: HashMap<Language, Profile> = {
+        let profiles: HashMap<String, RawProfile> = serde_json::from_str(PROFILES_JSON).unwrap();
+        profiles.into_iter()
+            .filter_map(|(name, profile)| {
+                let language = name.parse::<Language>().ok()?;
+                Some((language, Profile {
+                    ngrams: profile.ngrams.into_iter().enumerate().map(|(rank, ngram)| (ngram, rank)).collect(),
+                    words: profile.words.into_iter().collect(),
+                }))
+            })
+            .collect()
+    };
+}
+
+/// Offline language identifier restricted to the supported languages.
+///
+/// Languages with their own script (Greek) are recognised by it; the others are compared by the rank of their most
+/// frequent character n-grams (Cavnar & Trenkle).
+#[derive(Debug, Clone)]
+pub struct LanguageDetector {
+    languages: Vec<Language>,
+}
+
+impl Default for LanguageDetector {
+    fn default() -> Self {
+        LanguageDetector { languages: Language::iter().filter(|language| !matches!(language, Language::Custom(_))).collect() }
+    }
+}
+
+impl LanguageDetector {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only considers `languages`.
+    pub fn with_languages(languages: &[Language]) -> Self {
+        LanguageDetector { languages: languages.to_vec() }
+    }
+
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    /// The most likely language, `None` when the text has no letters or
+    /// none of the candidates fits.
+    pub fn detect(&self, text: &str) -> Option<Language> {
+        if let Some(language) = self._detect_script(text) {
+            return Some(language);
+        }
+        self.distances(text).first().map(|(language, _)| *language)
+    }
+
+    /// Mean out-of-place distance of the text's n-grams to each candidate
+    /// with a profile, lowered for every function word of the candidate;
+    /// closest first.
+    pub fn distances(&self, text: &str) -> Vec<(Language, f64)> {
+        let ngrams = _ranked_ngrams(text);
+        if ngrams.is_empty() {
+            return Vec::new();
+        }
+        let words: Vec<String> = _words(text).map(|word| word.to_lowercase()).collect();
+
+        let mut distances: Vec<(Language, f64)> = self.languages.iter()
+            .filter_map(|language| {
+                let profile = PROFILES.get(language)?;
+                let out_of_place: usize = ngrams.iter().enumerate()
+                    .map(|(rank, ngram)| match profile.ngrams.get(ngram) {
+                        Some(profile_rank) => (rank as isize - *profile_rank as isize).unsigned_abs(),
+                        None => PROFILE_SIZE
+                    })
+                    .sum();
+                // no n-gram in common, e.g. another script
+                if out_of_place == ngrams.len() * PROFILE_SIZE {
+                    return None;
+                }
+                let common_words = words.iter().filter(|word| profile.words.contains(*word)).count();
+
+                Some((*language, out_of_place as f64 / ngrams.len() as f64 - WORD_BONUS * common_words as f64))
+            })
+            .collect();
+
+        distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        distances
+    }
+
+    fn _detect_script(&self, text: &str) -> Option<Language> {
+        let mut letters = 0;
+        let mut scripts: HashMap<Language, usize> = HashMap::new();
+
+        for c in text.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            let language = match c {
+                '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Language::Greek,
+                _ => continue
+            };
+            *scripts.entry(language).or_insert(0) += 1;
+        }
+
+        scripts.into_iter()
+            .filter(|(language, count)| count * 2 > letters && self.languages.contains(language))
+            .map(|(language, _)| language)
+            .next()
+    }
+}
+
+/// Detects the language of `text` among all built-in languages.
+pub fn detect_language(text: &str) -> Option<Language> {
+    LanguageDetector::default().detect(text)
+}
+
+fn _words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic()).filter(|word| !word.is_empty())
+}
+
+fn _ranked_ngrams(text: &str) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for word in _words(text) {
+        let padded: Vec<char> = format!(" {} ", word.to_lowercase()).chars().collect();
+        for n in 1..=3 {
+            for ngram in padded.windows(n) {
+                let ngram: String = ngram.iter().collect();
+                if !ngram.trim().is_empty() {
+                    *counts.entry(ngram).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.into_iter().take(PROFILE_SIZE).map(|(ngram, _)| ngram).collect()
+}
+
+
+#[cfg(test)]
+mod language_detection_tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_latin_script_languages() {
+        let samples = [
+            (Language::English, "The quick brown fox jumps over the lazy dog. He went to the store yesterday."),
+            (Language::German, "Der schnelle braune Fuchs springt über den faulen Hund. Er ging gestern in den Laden."),
+            (Language::French, "Le renard brun rapide saute par-dessus le chien paresseux. Il est allé au magasin hier."),
+            (Language::Spanish, "El rápido zorro marrón salta sobre el perro perezoso. Ayer fue a la tienda."),
+            (Language::Italian, "La volpe marrone veloce salta sopra il cane pigro. Ieri è andato al negozio."),
+            (Language::Portuguese, "A rápida raposa marrom pula sobre o cão preguiçoso. Ontem ele foi à loja."),
+            (Language::Dutch, "De snelle bruine vos springt over de luie hond. Hij ging gisteren naar de winkel."),
+            (Language::Danish, "Den hurtige brune ræv springer over den dovne hund. Han gik i butikken i går."),
+            (Language::Norwegian, "Den raske brune reven hopper over den late hunden. Han gikk i butikken i går."),
+            (Language::Swedish, "Den snabba bruna räven hoppar över den lata hunden. Han gick till affären igår."),
+            (Language::Finnish, "Nopea ruskea kettu hyppää laiskan koiran yli. Hän meni eilen kauppaan."),
+            (Language::Estonian, "Kiire pruun rebane hüppab üle laisa koera. Ta läks eile poodi."),
+            (Language::Polish, "Szybki brązowy lis skacze nad leniwym psem. Wczoraj poszedł do sklepu."),
+            (Language::Czech, "Rychlá hnědá liška skáče přes líného psa. Včera šel do obchodu."),
+            (Language::Slovenian, "Hitra rjava lisica skoči čez lenega psa. Včeraj je šel v trgovino."),
+            (Language::Turkish, "Hızlı kahverengi tilki tembel köpeğin üzerinden atlar. Dün markete gitti."),
+        ];
+
+        for (language, text) in samples.iter() {
+            assert_eq!(detect_language(text), Some(*language), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_detect_short_texts() {
+        let samples = [
+            (Language::English, "The costs are too high."),
+            (Language::German, "Das Treffen war gut."),
+            (Language::French, "Je n'ai pas le temps."),
+            (Language::Spanish, "Los costos son demasiado altos."),
+            (Language::Italian, "Non ho tempo oggi."),
+            (Language::Swedish, "Jag har ingen tid idag."),
+            (Language::Czech, "Uvidíme se zítra."),
+        ];
+
+        for (language, text) in samples.iter() {
+            assert_eq!(detect_language(text), Some(*language), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_detect_by_script() {
+        assert_eq!(detect_language("Η γρήγορη καφέ αλεπού πηδάει πάνω από τον σκύλο."), Some(Language::Greek));
+        // no supported language is written in Cyrillic
+        assert_eq!(detect_language("Быстрая коричневая лиса прыгает через ленивую собаку."), None);
+    }
+
+    #[test]
+    fn test_restricted_languages() {
+        let detector = LanguageDetector::with_languages(&[Language::Danish, Language::Swedish]);
+
+        assert_eq!(detector.detect("Den raske brune reven hopper over den late hunden."), Some(Language::Danish));
+        assert_eq!(detector.detect("Быстрая коричневая лиса."), None);
+        assert_eq!(detector.distances("Den snabba bruna räven.").len(), 2);
+    }
+
+    #[test]
+    fn test_no_letters() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("42 + 7 = 49"), None);
+    }
+}
@@ -7,8 +7,37 @@ pub enum SentenceSplitterError {
 
     #[error("IO error: {0}")]
     IOError(String),
+
+    #[error("Offline mode is enabled and the resource is not cached: {0}")]
+    OfflineError(String),
+
+    #[error("Checksum verification failed: {0}")]
+    ChecksumError(String),
+
+    #[error("Invalid weight file: {0}")]
+    WeightFileError(String),
+
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
+    #[error("Invalid bundle: {0}")]
+    BundleError(String),
+
+    #[error("Cannot register language: {0}")]
+    LanguageError(String),
+
+    #[error("The `{0}` feature is not enabled")]
+    FeatureError(String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown language `{input}`, supported languages: {}", .supported.join(", "))]
+pub struct LanguageParseError {
+    pub input: String,
+    pub supported: Vec<String>,
 }
 
+#[cfg(feature = "remote")]
 impl From<cached_path::Error> for SentenceSplitterError {
     fn from(error: cached_path::Error) -> Self {
         SentenceSplitterError::FileDownloadError(error.to_string())
@@ -20,3 +49,9 @@ impl From<std::io::Error> for SentenceSplitterError {
         SentenceSplitterError::IOError(error.to_string())
     }
 }
+
+impl From<serde_json::Error> for SentenceSplitterError {
+    fn from(error: serde_json::Error) -> Self {
+        SentenceSplitterError::WeightFileError(error.to_string())
+    }
+}
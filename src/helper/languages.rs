@@ -0,0 +1,219 @@
+use crate::helper::common::{CustomLanguageId, Language};
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::resources::Resource;
+use crate::tokenize::profile::LanguageProfile;
+use crate::tokenize::registry;
+
+use lazy_static::lazy_static;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, RwLock};
+
+
+/// A language registered at runtime, used by the tokenizer constructor like
+/// a built-in one.
+#[derive(Clone)]
+pub struct CustomLanguage {
+    pub name: &'static str,
+    pub iso_code: &'static str,
+    pub profile: LanguageProfile,
+    pub weights: Resource,
+}
+
+lazy_static! {
+    static ref CUSTOM_LANGUAGES: RwLock<HashMap<&'static str, CustomLanguage>> = RwLock::new(HashMap::new());
+    /// Every name and code leaked so far, so that registering a language
+    /// again does not leak them again.
+    static ref LEAKED: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+/// Registers `name` with its ISO code, profile and weight file and returns
+/// the `Language` to pass to `PunktSentenceTokenizer::new`. Registering a
+/// name again replaces its settings and evicts its shared tokenizer.
+///
+/// Fails when the name or the code already stands for another language,
+/// built-in ones included, e.g. `"English"`, `"slovene"` or `"en"`.
+pub fn register_language(name: &str, iso_code: &str, profile: LanguageProfile, weights: Resource) -> Result<Language, SentenceSplitterError> {
+    for input in [name, iso_code] {
+        if let Some(language) = _builtin_language(input) {
+            return Err(SentenceSplitterError::LanguageError(format!("`{}` stands for {}", input, language)));
+        }
+    }
+
+    let mut languages = CUSTOM_LANGUAGES.write().unwrap();
+    for input in [name, iso_code] {
+        if let Some(other) = languages.values().find(|other| other.name != name && _matches(other, input)) {
+            return Err(SentenceSplitterError::LanguageError(format!("`{}` stands for {}", input, other.name)));
+        }
+    }
+    let name = _leak(name);
+    languages.insert(name, CustomLanguage { name, iso_code: _leak(iso_code), profile, weights });
+    // loading a model reads this registry, evict once it is released
+    drop(languages);
+    let language = Language::Custom(CustomLanguageId::new(name));
+    registry::evict(language);
+    Ok(language)
+}
+
+/// Removes a registered language. Returns false when `name` is unknown.
+pub fn unregister_language(name: &str) -> bool {
+    let removed = CUSTOM_LANGUAGES.write().unwrap().remove(name);
+    match removed {
+        Some(language) => {
+            registry::evict(Language::Custom(CustomLanguageId::new(language.name)));
+            true
+        }
+        None => false
+    }
+}
+
+/// Settings of a registered language, `None` for built-in or unregistered ones.
+pub fn get_custom_language(language: &Language) -> Option<CustomLanguage> {
+    match language {
+        Language::Custom(id) => CUSTOM_LANGUAGES.read().unwrap().get(id.name()).cloned(),
+        _ => None
+    }
+}
+
+/// All registered languages, sorted by name.
+pub fn custom_languages() -> Vec<Language> {
+    let mut names: Vec<&'static str> = CUSTOM_LANGUAGES.read().unwrap().keys().copied().collect();
+    names.sort_unstable();
+    names.into_iter().map(|name| Language::Custom(CustomLanguageId::new(name))).collect()
+}
+
+/// The built-in language `input` parses to, as a name, alias or code.
+fn _builtin_language(input: &str) -> Option<Language> {
+    match input.parse::<Language>() {
+        Ok(Language::Custom(_)) | Err(_) => None,
+        Ok(language) => Some(language),
+    }
+}
+
+/// Whether parsing `input` could find `language`, like `Language::from_str`
+/// compares names and codes.
+fn _matches(language: &CustomLanguage, input: &str) -> bool {
+    let input = input.trim().to_lowercase();
+    let primary_subtag = input.split(['-', '_']).next().unwrap_or(&input).to_string();
+    [input, primary_subtag].iter()
+        .any(|input| language.name.to_lowercase() == *input || language.iso_code.to_lowercase() == *input)
+}
+
+/// Registered names and codes live as long as the process, which keeps
+/// `Language` `Copy`. Each distinct string is leaked once.
+fn _leak(s: &str) -> &'static str {
+    let mut leaked = LEAKED.lock().unwrap();
+    match leaked.get(s) {
+        Some(s) => s,
+        None => {
+            let s: &'static str = Box::leak(s.to_string().into_boxed_str());
+            leaked.insert(s);
+            s
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod custom_language_tests {
+    use super::*;
+    use crate::helper::resources::LocalResource;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn spanish_weights() -> Resource {
+        Resource::Local(LocalResource { local_path: PathBuf::from("data/weights/Spanish.json") })
+    }
+
+    #[test]
+    fn registered_language_is_found_by_name() {
+        let language = register_language("Catalan", "ca", LanguageProfile::new(), spanish_weights()).unwrap();
+
+        assert!(matches!(language, Language::Custom(id) if id.name() == "Catalan"));
+        assert_eq!(Language::from_str("Catalan"), Ok(language));
+        assert_eq!(Language::from_str("catalan"), Ok(language));
+        assert_eq!(Language::from_str("ca-ES"), Ok(language));
+        assert_eq!(language.to_string(), "Catalan");
+        assert_eq!(language.get_iso_639_1_code(), "ca");
+        assert!(custom_languages().contains(&language));
+    }
+
+    #[test]
+    fn tokenizer_uses_registered_weights_and_profile() {
+        let profile = LanguageProfile::new().paired_marks(&[('¿', '?'), ('¡', '!')]);
+        let language = register_language("LegalSpanish", "lsp", profile.clone(), spanish_weights()).unwrap();
+
+        assert_eq!(LanguageProfile::for_language(language), profile);
+
+        let tokenizer = PunktSentenceTokenizer::new(None, Some(language));
+        assert_eq!(
+            tokenizer.tokenize("¿Cómo estás? ¡Muy bien! Gracias por preguntar.", true),
+            vec!["¿Cómo estás?", "¡Muy bien!", "Gracias por preguntar."]
+        );
+    }
+
+    #[test]
+    fn tokenizer_uses_embedded_weights() {
+        let weights = Resource::Static(include_bytes!("../../data/weights/Spanish.json"));
+        let language = register_language("EmbeddedSpanish", "esx", LanguageProfile::new(), weights).unwrap();
+
+        let tokenizer = PunktSentenceTokenizer::new(None, Some(language));
+        assert_eq!(
+            tokenizer.tokenize("Llegó ayer. Se fue hoy.", true),
+            vec!["Llegó ayer.", "Se fue hoy."]
+        );
+    }
+
+    #[test]
+    fn registering_again_replaces_the_settings() {
+        let first = register_language("Occitan", "oc", LanguageProfile::new(), spanish_weights()).unwrap();
+        let language = register_language("Occitan", "oci", LanguageProfile::new().quote_aware(true), spanish_weights()).unwrap();
+
+        assert_eq!(language, first);
+        assert_eq!(language.get_iso_639_1_code(), "oci");
+        assert!(get_custom_language(&language).unwrap().profile.quote_aware);
+
+        let oc = register_language("Occitan", "oc", LanguageProfile::new(), spanish_weights()).unwrap().get_iso_639_1_code();
+        register_language("Occitan", "oci", LanguageProfile::new(), spanish_weights()).unwrap();
+        // the codes were leaked once
+        assert!(std::ptr::eq(register_language("Occitan", "oc", LanguageProfile::new(), spanish_weights()).unwrap().get_iso_639_1_code(), oc));
+    }
+
+    #[test]
+    fn builtin_names_and_codes_are_rejected() {
+        for (name, iso_code) in [("English", "xx"), ("english", "xx"), ("slovene", "xx"), ("Klingon", "en"), ("Klingon", "pt-BR"), ("Klingon", "deu")] {
+            assert!(matches!(
+                register_language(name, iso_code, LanguageProfile::new(), spanish_weights()),
+                Err(SentenceSplitterError::LanguageError(_))
+            ), "{} {}", name, iso_code);
+        }
+
+        assert_eq!(Language::English.get_iso_639_1_code(), "en");
+        assert!(Language::from_str("Klingon").is_err());
+    }
+
+    #[test]
+    fn names_and_codes_of_registered_languages_are_rejected() {
+        let basque = register_language("Basque", "eu", LanguageProfile::new(), spanish_weights()).unwrap();
+
+        for (name, iso_code) in [("Euskara", "eu"), ("Euskara", "eu-ES"), ("basque", "eus"), ("EU", "eus")] {
+            assert!(matches!(
+                register_language(name, iso_code, LanguageProfile::new(), spanish_weights()),
+                Err(SentenceSplitterError::LanguageError(_))
+            ), "{} {}", name, iso_code);
+        }
+        assert!(Language::from_str("Euskara").is_err());
+        assert_eq!(Language::from_str("eu"), Ok(basque));
+    }
+
+    #[test]
+    fn unregistered_language_is_not_parsed() {
+        let language = register_language("Aragonese", "an", LanguageProfile::new(), spanish_weights()).unwrap();
+
+        assert!(unregister_language("Aragonese"));
+        assert!(!unregister_language("Aragonese"));
+        assert!(Language::from_str("Aragonese").is_err());
+        // not looked up among the built-in weights
+        assert!(matches!(PunktSentenceTokenizer::_language_weights(language), Err(SentenceSplitterError::LanguageError(_))));
+    }
+}
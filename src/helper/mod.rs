@@ -1,4 +1,11 @@
+pub mod bundle;
+#[cfg(feature = "remote")]
+pub mod cache;
 pub mod common;
+pub mod detection;
 pub mod errors;
+pub mod languages;
 pub mod resources;
-pub mod slice;
\ No newline at end of file
+pub mod slice;
+#[cfg(all(test, feature = "remote"))]
+pub(crate) mod test_server;
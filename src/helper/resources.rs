@@ -1,27 +1,80 @@
+use crate::helper::common::Language;
 use crate::helper::errors::SentenceSplitterError;
 
+#[cfg(feature = "remote")]
 use cached_path::{Cache, Options, ProgressBar};
+#[cfg(feature = "remote")]
+use fs2::FileExt;
+#[cfg(feature = "remote")]
 use lazy_static::lazy_static;
-use std::env;
-use std::path::PathBuf;
-
-extern crate dirs;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "remote")]
+use std::{collections::HashMap, env, fmt, io::IsTerminal, sync::{Arc, RwLock}, time::SystemTime};
 
 #[derive(PartialEq, Clone)]
 pub enum Resource {
     Local(LocalResource),
+    /// Downloaded into the cache, needs the `remote` feature.
+    #[cfg(feature = "remote")]
     Remote(RemoteResource),
+    /// File content held in memory, e.g. read from a database.
+    Bytes(Vec<u8>),
+    /// File content embedded in the binary with `include_bytes!`.
+    Static(&'static [u8]),
 }
 
 impl Resource {
+    /// The content of the resource, downloading it first if needed.
+    pub fn read(&self) -> Result<Cow<'_, [u8]>, SentenceSplitterError> {
+        match self {
+            Resource::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+            Resource::Static(bytes) => Ok(Cow::Borrowed(bytes)),
+            _ => Ok(Cow::Owned(fs::read(self.get_local_path()?)?))
+        }
+    }
+
+    /// Fails for in-memory resources, which have no path.
     pub fn get_local_path(&self) -> Result<PathBuf, SentenceSplitterError> {
         match self {
+            Resource::Bytes(_) | Resource::Static(_) => Err(SentenceSplitterError::IOError(
+                String::from("in-memory resources have no local path")
+            )),
             Resource::Local(resource) => Ok(resource.local_path.clone()),
+            #[cfg(feature = "remote")]
             Resource::Remote(resource) => {
-                let cached_path = CACHE.cached_path_with_options(
-                    &resource.url,
-                    &Options::default().subdir(&resource.cache_subdir),
-                )?;
+                // held until the file is verified, so that other processes
+                // neither download it again nor see it half written
+                let _lock = CacheLock::acquire(&resource.url, &resource.cache_subdir)?;
+                if let Some(imported_path) = resource._imported()? {
+                    return Ok(imported_path);
+                }
+                let cached_path = resource._fetch()?;
+                let expected = match &resource.sha256 {
+                    Some(expected) => expected,
+                    None => return Ok(cached_path)
+                };
+                if &sha256_file(&cached_path)? == expected {
+                    return Ok(cached_path);
+                }
+
+                // damaged or tampered, download it again
+                _emit(CacheEvent::ChecksumMismatch { url: resource.url.clone(), path: cached_path.clone() });
+                _remove_cached(&cached_path)?;
+                if is_offline() {
+                    return Err(SentenceSplitterError::OfflineError(resource.url.clone()));
+                }
+                let cached_path = resource._fetch()?;
+                let actual = sha256_file(&cached_path)?;
+                if &actual != expected {
+                    _emit(CacheEvent::ChecksumMismatch { url: resource.url.clone(), path: cached_path.clone() });
+                    _remove_cached(&cached_path)?;
+                    return Err(SentenceSplitterError::ChecksumError(
+                        format!("{}: expected SHA-256 {}, got {}", resource.url, expected, actual)
+                    ));
+                }
                 Ok(cached_path)
             }
         }
@@ -33,33 +86,424 @@ pub struct LocalResource {
     pub local_path: PathBuf,
 }
 
+#[cfg(feature = "remote")]
 #[derive(PartialEq, Clone)]
 pub struct RemoteResource {
     pub url: String,
     pub cache_subdir: String,
+    /// Hex digest the downloaded file is verified against, after the
+    /// download and whenever it is read from the cache.
+    pub sha256: Option<String>,
 }
 
+#[cfg(feature = "remote")]
 impl RemoteResource {
     pub fn new(url: &str, cache_subdir: &str) -> RemoteResource {
         RemoteResource {
             url: url.to_string(),
             cache_subdir: cache_subdir.to_string(),
+            sha256: None,
+        }
+    }
+
+    pub fn with_sha256(mut self, sha256: &str) -> RemoteResource {
+        self.sha256 = Some(sha256.to_lowercase());
+        self
+    }
+
+    /// The copy imported from a bundle, used instead of downloading as long
+    /// as it matches the digest. A mismatching copy is removed.
+    fn _imported(&self) -> Result<Option<PathBuf>, SentenceSplitterError> {
+        let imported_path = _imported_path(&self.url, &self.cache_subdir);
+        if !imported_path.is_file() {
+            return Ok(None);
+        }
+        match &self.sha256 {
+            Some(expected) if &sha256_file(&imported_path)? != expected => {
+                _emit(CacheEvent::ChecksumMismatch { url: self.url.clone(), path: imported_path.clone() });
+                _remove_cached(&imported_path)?;
+                Ok(None)
+            }
+            _ => {
+                _emit(CacheEvent::CacheHit { url: self.url.clone(), path: imported_path.clone() });
+                Ok(Some(imported_path))
+            }
+        }
+    }
+
+    /// Resolves the resource through the cache and reports whether it was
+    /// downloaded.
+    fn _fetch(&self) -> Result<PathBuf, SentenceSplitterError> {
+        // `cached-path` does not tell, but a download writes a new `.meta`
+        let directory = CACHE.dir.join(&self.cache_subdir);
+        let before = _meta_times(&directory);
+        let cached_path = self._cached_path()?;
+        let meta_path = _meta_path(&cached_path);
+        let downloaded = match before.get(&meta_path) {
+            Some(modified) => fs::metadata(&meta_path).and_then(|metadata| metadata.modified()).ok().as_ref() != Some(modified),
+            None => true
+        };
+
+        _emit(if downloaded {
+            CacheEvent::Downloaded {
+                url: self.url.clone(),
+                size: fs::metadata(&cached_path)?.len(),
+                path: cached_path.clone(),
+            }
+        } else {
+            CacheEvent::CacheHit { url: self.url.clone(), path: cached_path.clone() }
+        });
+        Ok(cached_path)
+    }
+
+    fn _cached_path(&self) -> Result<PathBuf, SentenceSplitterError> {
+        let options = Options::default().subdir(&self.cache_subdir);
+        if is_offline() {
+            return OFFLINE_CACHE.cached_path_with_options(&self.url, &options)
+                .map_err(|error| match error {
+                    cached_path::Error::NoCachedVersions(url) => SentenceSplitterError::OfflineError(url),
+                    error => error.into()
+                });
         }
+        let cache: &Cache = if shows_progress_bar() { &PROGRESS_CACHE } else { &CACHE };
+        Ok(cache.cached_path_with_options(&self.url, &options)?)
     }
 }
 
+/// Exclusive lock on a remote resource, shared by every process using the
+/// cache. Released when dropped.
+#[cfg(feature = "remote")]
+struct CacheLock {
+    file: fs::File,
+}
+
+#[cfg(feature = "remote")]
+impl CacheLock {
+    fn acquire(url: &str, cache_subdir: &str) -> Result<CacheLock, SentenceSplitterError> {
+        let directory = CACHE.dir.join(cache_subdir);
+        fs::create_dir_all(&directory)?;
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(directory.join(format!("{}.lock", sha256_bytes(url.as_bytes()))))?;
+        file.lock_exclusive()?;
+        Ok(CacheLock { file })
+    }
+}
+
+#[cfg(feature = "remote")]
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Removes a cached download of `url` once no other process is using it.
+/// Its lock file is kept, other processes may be waiting on it.
+#[cfg(feature = "remote")]
+pub(crate) fn remove_from_cache(url: &str, cache_subdir: &str, cached_path: &Path) -> Result<(), SentenceSplitterError> {
+    let _lock = CacheLock::acquire(url, cache_subdir)?;
+    _remove_cached(cached_path)
+}
+
+/// Removes a cached file and its metadata, so that the next lookup
+/// downloads it again.
+#[cfg(feature = "remote")]
+fn _remove_cached(cached_path: &Path) -> Result<(), SentenceSplitterError> {
+    for path in [cached_path.to_path_buf(), _meta_path(cached_path)].iter() {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "remote")]
+fn _meta_path(cached_path: &Path) -> PathBuf {
+    let mut meta_path = cached_path.as_os_str().to_owned();
+    meta_path.push(".meta");
+    PathBuf::from(meta_path)
+}
+
+/// Modification times of the `.meta` files in `directory`.
+#[cfg(feature = "remote")]
+fn _meta_times(directory: &Path) -> HashMap<PathBuf, SystemTime> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return HashMap::new()
+    };
+    entries.filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "meta"))
+        .filter_map(|path| Some((path.clone(), fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?)))
+        .collect()
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, SentenceSplitterError> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(_hex(&hasher.finalize()))
+}
+
+pub fn sha256_bytes(bytes: &[u8]) -> String {
+    _hex(&Sha256::digest(bytes))
+}
+
+fn _hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Where a bundle import of `url` is stored. It is kept apart from the
+/// files of `cached-path`, whose layout and ETags are its own, and is
+/// looked up before them both online and offline.
+#[cfg(feature = "remote")]
+fn _imported_path(url: &str, cache_subdir: &str) -> PathBuf {
+    CACHE.dir.join(cache_subdir).join(format!("{}.imported", sha256_bytes(url.as_bytes())))
+}
+
+/// Stores `content` as the imported version of `url`, with a `.meta` file
+/// naming the URL like the ones of downloads.
+#[cfg(feature = "remote")]
+pub(crate) fn store_in_cache(url: &str, cache_subdir: &str, content: &[u8]) -> Result<PathBuf, SentenceSplitterError> {
+    let _lock = CacheLock::acquire(url, cache_subdir)?;
+    let resource_path = _imported_path(url, cache_subdir);
+    let meta = serde_json::json!({
+        "resource": url,
+        "resource_path": resource_path,
+    });
+
+    let mut partial = resource_path.as_os_str().to_owned();
+    partial.push(".partial");
+    fs::write(&partial, content)?;
+    fs::rename(&partial, &resource_path)?;
+    fs::write(_meta_path(&resource_path), meta.to_string())?;
+    _emit(CacheEvent::Imported { url: url.to_string(), path: resource_path.clone() });
+    Ok(resource_path)
+}
+
+/// SHA-256 of the weight file of a built-in language in `data/weights`.
+/// Changed weights are published with a new release tag, older versions
+/// keep downloading their own.
+pub fn weights_sha256(language: Language) -> Option<&'static str> {
+    match language {
+        Language::Czech => Some("bb8d8eb01900137288704f2cbb22fde9bc27c5ab05a4dda73c2f2b992fb4c3e5"),
+        Language::Danish => Some("8970c23e2ce2d8a2cd10db91c3d82d9f5c32f68dfc5a7f76e04c414f88894349"),
+        Language::Dutch => Some("79e04319208a601ef019cbf3111084db46516bd863bf9e5139dde647572b59d0"),
+        Language::English => Some("e364887497702f6e083685324ca8501b17fbb6ccbda30eea45986a1f99681698"),
+        Language::Estonian => Some("0a2cf372424314616abe9f325d61944c3a73846c1aa10ef65958b2f328398867"),
+        Language::Finnish => Some("2f431abc081e353ab5a5f1151c751d19fa722e649808fc41da7f59dbafba6ba3"),
+        Language::French => Some("98957000267349d8314feeba3dc2d4e0a1b19d89b1e3b13b3c2005cee7d365bf"),
+        Language::German => Some("782c1b849286e9fd81eaabd00492bd38718a67bc8fd77927608a4ac5b0ee6369"),
+        Language::Greek => Some("e94ddce2f863aef3a0cdb68322d49b50d228833d5dea36acf163e0ab108ee3a0"),
+        Language::Italian => Some("1235af22c310c8811b52b4d375203055bb25e2991ae1fa6165f9bf970e4018d7"),
+        Language::Norwegian => Some("3039561d74d422f7c0cc59d946c098a990f286eb20c587cf273cc9030c0352a3"),
+        Language::Polish => Some("e8ca0c69668ed7f575f3731124c00d3212e84ae427dfeb7b6cae5b5907b7b117"),
+        Language::Portuguese => Some("186d45eff81452da1a0370f93c85e2f644bea061af5969288f524c2851435ca2"),
+        Language::Slovenian => Some("f43965690708f67b177968a4150dbe131c45c992c5fd93924ce1654bcf52d1d9"),
+        Language::Spanish => Some("3ecf0e30a8f16f162f87d5771254711b911a8201672c63fcc1ba223b059f563c"),
+        Language::Swedish => Some("3aa6bece5c45cb68724d932a20e006b13e01c01c634b62a6f292699563bf9311"),
+        Language::Turkish => Some("038f4e34365709bde0f21d3f745f23aad9377aee9e5f8705719e0cededc958a2"),
+        Language::Custom(_) => None
+    }
+}
+
+#[cfg(feature = "remote")]
 lazy_static! {
     #[derive(Copy, Clone, Debug)]
     pub static ref CACHE: Cache = Cache::builder()
+        .dir(_get_cache_directory())
+        .progress_bar(None)
+        .build().unwrap();
+
+    /// Like `CACHE`, printing download progress to stderr.
+    static ref PROGRESS_CACHE: Cache = Cache::builder()
         .dir(_get_cache_directory())
         .progress_bar(Some(ProgressBar::Light))
         .build().unwrap();
+
+    /// Resolves remote resources from the cache only.
+    static ref OFFLINE_CACHE: Cache = Cache::builder()
+        .dir(_get_cache_directory())
+        .offline(true)
+        .build().unwrap();
+
+    static ref SETTINGS: RwLock<Settings> = RwLock::new(Settings::default());
 }
 
-fn _get_cache_directory() -> PathBuf {
-    match env::var("SENTENCE_SPLITTER_CACHE") {
+#[cfg(feature = "remote")]
+/// Where the weights of the built-in languages are downloaded from: the
+/// release tag of this version, whose files match `weights_sha256`.
+pub static DEFAULT_BASE_URL: &str = concat!(
+    "https://raw.githubusercontent.com/Kavan72/sentence-splitter/v", env!("CARGO_PKG_VERSION"), "/data/weights"
+);
+
+#[cfg(feature = "remote")]
+#[derive(Default)]
+struct Settings {
+    base_url: Option<String>,
+    offline: Option<bool>,
+    progress_bar: Option<bool>,
+    listener: Option<Arc<CacheListener>>,
+}
+
+#[cfg(feature = "remote")]
+/// Downloads weights from `base_url` (a mirror of `data/weights`) instead of
+/// GitHub. Takes precedence over the `SENTENCE_SPLITTER_BASE_URL` environment
+/// variable.
+pub fn set_base_url(base_url: &str) {
+    SETTINGS.write().unwrap().base_url = Some(base_url.trim_end_matches('/').to_string());
+}
+
+#[cfg(feature = "remote")]
+pub fn base_url() -> String {
+    if let Some(base_url) = &SETTINGS.read().unwrap().base_url {
+        return base_url.clone();
+    }
+    match env::var("SENTENCE_SPLITTER_BASE_URL") {
+        Ok(value) if !value.is_empty() => value.trim_end_matches('/').to_string(),
+        _ => DEFAULT_BASE_URL.to_string()
+    }
+}
+
+#[cfg(all(test, feature = "remote"))]
+pub(crate) fn reset_settings() {
+    *SETTINGS.write().unwrap() = Settings::default();
+}
+
+#[cfg(feature = "remote")]
+/// URL of the weight file of a built-in language.
+pub fn weights_url(language: Language) -> String {
+    format!("{}/{}.json", base_url(), language)
+}
+
+#[cfg(feature = "remote")]
+/// In offline mode remote resources are only resolved from the cache, and
+/// `SentenceSplitterError::OfflineError` is returned for the others. Takes
+/// precedence over the `SENTENCE_SPLITTER_OFFLINE` environment variable.
+pub fn set_offline(offline: bool) {
+    SETTINGS.write().unwrap().offline = Some(offline);
+}
+
+#[cfg(feature = "remote")]
+pub fn is_offline() -> bool {
+    if let Some(offline) = SETTINGS.read().unwrap().offline {
+        return offline;
+    }
+    _env_flag("SENTENCE_SPLITTER_OFFLINE").unwrap_or(false)
+}
+
+#[cfg(feature = "remote")]
+/// Whether downloads print a progress bar to stderr. Takes precedence over
+/// the `SENTENCE_SPLITTER_PROGRESS` environment variable; without either the
+/// bar is shown when stderr is a terminal.
+pub fn set_progress_bar(progress_bar: bool) {
+    SETTINGS.write().unwrap().progress_bar = Some(progress_bar);
+}
+
+#[cfg(feature = "remote")]
+pub fn shows_progress_bar() -> bool {
+    if let Some(progress_bar) = SETTINGS.read().unwrap().progress_bar {
+        return progress_bar;
+    }
+    _env_flag("SENTENCE_SPLITTER_PROGRESS").unwrap_or_else(|| io::stderr().is_terminal())
+}
+
+#[cfg(feature = "remote")]
+fn _env_flag(name: &str) -> Option<bool> {
+    env::var(name).ok().map(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+}
+
+#[cfg(feature = "remote")]
+/// What happened to a remote resource. Every event is also logged with the
+/// `log` crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheEvent {
+    /// Sent once the download is complete. There are no events while it
+    /// runs, `cached-path` only reports progress on its own progress bar.
+    Downloaded { url: String, path: PathBuf, size: u64 },
+    /// The cached version was current.
+    CacheHit { url: String, path: PathBuf },
+    /// The cached file did not match its digest and was removed.
+    ChecksumMismatch { url: String, path: PathBuf },
+    /// Stored from a bundle.
+    Imported { url: String, path: PathBuf },
+}
+
+#[cfg(feature = "remote")]
+impl fmt::Display for CacheEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheEvent::Downloaded { url, path, size } => write!(f, "downloaded {} ({} bytes) to {}", url, size, path.display()),
+            CacheEvent::CacheHit { url, path } => write!(f, "{} is cached at {}", url, path.display()),
+            CacheEvent::ChecksumMismatch { url, path } => write!(f, "{} at {} does not match its digest", url, path.display()),
+            CacheEvent::Imported { url, path } => write!(f, "imported {} to {}", url, path.display()),
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+type CacheListener = dyn Fn(&CacheEvent) + Send + Sync;
+
+#[cfg(feature = "remote")]
+/// Calls `listener` with every cache event, e.g. to report downloads from a
+/// service that has no terminal. Replaces the previous listener.
+pub fn set_cache_listener<F: Fn(&CacheEvent) + Send + Sync + 'static>(listener: F) {
+    SETTINGS.write().unwrap().listener = Some(Arc::new(listener));
+}
+
+#[cfg(feature = "remote")]
+pub fn clear_cache_listener() {
+    SETTINGS.write().unwrap().listener = None;
+}
+
+#[cfg(feature = "remote")]
+fn _emit(event: CacheEvent) {
+    match &event {
+        CacheEvent::ChecksumMismatch { .. } => log::warn!("{}", event),
+        CacheEvent::CacheHit { .. } => log::debug!("{}", event),
+        _ => log::info!("{}", event),
+    }
+    // called without holding the settings, which the listener may change
+    let listener = SETTINGS.read().unwrap().listener.clone();
+    if let Some(listener) = listener {
+        listener(&event);
+    }
+}
+
+#[cfg(all(test, feature = "remote"))]
+lazy_static! {
+    /// Cache of the test process, never the user's. Child processes started
+    /// by a test share the one in `SENTENCE_SPLITTER_TEST_CACHE`.
+    static ref TEST_CACHE_DIRECTORY: PathBuf = match env::var("SENTENCE_SPLITTER_TEST_CACHE") {
         Ok(value) => PathBuf::from(value),
         Err(_) => {
+            let directory = env::temp_dir().join(format!("sentence-splitter-tests-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&directory);
+            directory
+        }
+    };
+}
+
+#[cfg(all(test, feature = "remote"))]
+fn _get_cache_directory() -> PathBuf {
+    TEST_CACHE_DIRECTORY.clone()
+}
+
+#[cfg(all(not(test), feature = "remote"))]
+fn _get_cache_directory() -> PathBuf {
+    _cache_directory(env::var("SENTENCE_SPLITTER_CACHE").ok())
+}
+
+/// `configured` is the value of `SENTENCE_SPLITTER_CACHE`.
+#[cfg(feature = "remote")]
+fn _cache_directory(configured: Option<String>) -> PathBuf {
+    match configured {
+        Some(value) => PathBuf::from(value),
+        None => {
             let mut home = dirs::home_dir().unwrap();
             home.push(".cache");
             home.push(".sentence-splitter");
@@ -67,3 +511,203 @@ fn _get_cache_directory() -> PathBuf {
         }
     }
 }
+
+
+#[cfg(test)]
+mod resource_tests {
+    use super::*;
+
+    #[test]
+    fn test_read_in_memory_resources() {
+        static WEIGHTS: &[u8] = b"{\"abbrev_types\": []}";
+
+        assert_eq!(&*Resource::Static(WEIGHTS).read().unwrap(), WEIGHTS);
+        assert_eq!(&*Resource::Bytes(WEIGHTS.to_vec()).read().unwrap(), WEIGHTS);
+        assert!(matches!(Resource::Static(WEIGHTS).get_local_path(), Err(SentenceSplitterError::IOError(_))));
+        assert_eq!(
+            Resource::Local(LocalResource { local_path: PathBuf::from("data/weights/English.json") }).read().unwrap().len(),
+            fs::metadata("data/weights/English.json").unwrap().len() as usize
+        );
+    }
+}
+
+#[cfg(all(test, feature = "remote"))]
+mod remote_resource_tests {
+    use super::*;
+    use crate::helper::test_server::{remote_test_guard, TestServer};
+    use std::fs;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_base_url() {
+        let _guard = remote_test_guard();
+
+        set_base_url("http://mirror.example/weights/");
+        assert_eq!(weights_url(Language::Portuguese), "http://mirror.example/weights/Portuguese.json");
+
+        reset_settings();
+        env::set_var("SENTENCE_SPLITTER_BASE_URL", "http://env.example");
+        assert_eq!(weights_url(Language::German), "http://env.example/German.json");
+
+        env::remove_var("SENTENCE_SPLITTER_BASE_URL");
+        assert_eq!(base_url(), DEFAULT_BASE_URL);
+        assert!(DEFAULT_BASE_URL.contains(&format!("/v{}/", env!("CARGO_PKG_VERSION"))));
+    }
+
+    #[test]
+    fn test_download_from_mirror() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/weights/English.json", b"{}".to_vec())]);
+
+        set_base_url(&format!("{}/weights", server.url));
+        let resource = Resource::Remote(RemoteResource::new(&weights_url(Language::English), "punkt/English"));
+        let path = resource.get_local_path().unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "{}");
+        assert_eq!(server.downloads(), 1);
+    }
+
+    #[test]
+    fn test_weights_sha256() {
+        for language in Language::iter() {
+            if let Some(expected) = weights_sha256(language) {
+                let path = PathBuf::from(format!("data/weights/{}.json", language));
+                assert_eq!(sha256_file(&path).unwrap(), expected, "{}", language);
+            }
+        }
+    }
+
+    #[test]
+    fn test_damaged_cache_is_downloaded_again() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/English.json", b"{}".to_vec())]);
+        let resource = Resource::Remote(
+            RemoteResource::new(&format!("{}/English.json", server.url), "punkt/English")
+                .with_sha256("44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a")
+        );
+
+        let path = resource.get_local_path().unwrap();
+        fs::write(&path, "{\"abbrev_types\": [\"tampered\"]}").unwrap();
+        let path = resource.get_local_path().unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "{}");
+        assert_eq!(server.downloads(), 2);
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/English.json", b"{\"truncat".to_vec())]);
+        let resource = Resource::Remote(
+            RemoteResource::new(&format!("{}/English.json", server.url), "punkt/English")
+                .with_sha256("44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a")
+        );
+
+        match resource.get_local_path() {
+            Err(SentenceSplitterError::ChecksumError(message)) => assert!(message.contains("44136fa3")),
+            _ => panic!("expected a checksum error")
+        }
+        assert_eq!(server.downloads(), 2);
+    }
+
+    #[test]
+    fn test_offline_mode() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/English.json", b"{}".to_vec())]);
+        let cached = Resource::Remote(RemoteResource::new(&format!("{}/English.json", server.url), "punkt/English"));
+        let missing = Resource::Remote(RemoteResource::new(&format!("{}/German.json", server.url), "punkt/German"));
+        cached.get_local_path().unwrap();
+
+        set_offline(true);
+        let cached_path = cached.get_local_path();
+        let missing_path = missing.get_local_path();
+        set_offline(false);
+
+        assert!(cached_path.is_ok());
+        assert!(matches!(missing_path, Err(SentenceSplitterError::OfflineError(_))));
+        assert_eq!(server.downloads(), 1);
+    }
+
+    #[test]
+    fn test_cache_events() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/Dutch.json", b"{}".to_vec())]);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        set_cache_listener(move |event| received.lock().unwrap().push(event.clone()));
+
+        let url = format!("{}/Dutch.json", server.url);
+        let resource = Resource::Remote(RemoteResource::new(&url, "events"));
+        let path = resource.get_local_path().unwrap();
+        resource.get_local_path().unwrap();
+        let damaged = Resource::Remote(RemoteResource::new(&url, "events").with_sha256(&"0".repeat(64)));
+        let _ = damaged.get_local_path();
+        clear_cache_listener();
+        resource.get_local_path().unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events[0], CacheEvent::Downloaded { url: url.clone(), path: path.clone(), size: 2 });
+        assert_eq!(events[1], CacheEvent::CacheHit { url: url.clone(), path: path.clone() });
+        assert_eq!(events[2], CacheEvent::CacheHit { url: url.clone(), path: path.clone() });
+        assert_eq!(events[3], CacheEvent::ChecksumMismatch { url: url.clone(), path: path.clone() });
+        assert!(matches!(events[4], CacheEvent::Downloaded { .. }));
+        assert!(matches!(events[5], CacheEvent::ChecksumMismatch { .. }));
+        assert_eq!(events.len(), 6);
+    }
+
+    #[test]
+    fn test_cache_directory() {
+        assert_eq!(_cache_directory(Some(String::from("/srv/weights"))), PathBuf::from("/srv/weights"));
+        assert_eq!(_cache_directory(None), dirs::home_dir().unwrap().join(".cache").join(".sentence-splitter"));
+    }
+
+    #[test]
+    fn test_progress_bar_setting() {
+        let _guard = remote_test_guard();
+
+        set_progress_bar(true);
+        assert!(shows_progress_bar());
+        set_progress_bar(false);
+        assert!(!shows_progress_bar());
+    }
+
+    /// Run as a child process by `test_concurrent_first_use`, does nothing
+    /// otherwise.
+    #[test]
+    fn concurrent_first_use_child() {
+        let url = match env::var("SENTENCE_SPLITTER_TEST_URL") {
+            Ok(url) => url,
+            Err(_) => return
+        };
+        let resource = Resource::Remote(RemoteResource::new(&url, "punkt/English").with_sha256(
+            weights_sha256(Language::English).unwrap()
+        ));
+        let content = fs::read(resource.get_local_path().unwrap()).unwrap();
+
+        assert_eq!(content, fs::read("data/weights/English.json").unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_first_use() {
+        let _guard = remote_test_guard();
+        let server = TestServer::serve(vec![("/English.json", fs::read("data/weights/English.json").unwrap())]);
+        let cache_directory = env::temp_dir().join(format!("sentence-splitter-concurrent-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_directory);
+
+        let children: Vec<_> = (0..8).map(|_| {
+            std::process::Command::new(env::current_exe().unwrap())
+                .args(["--exact", "helper::resources::remote_resource_tests::concurrent_first_use_child", "--test-threads=1"])
+                .env("SENTENCE_SPLITTER_TEST_CACHE", &cache_directory)
+                .env("SENTENCE_SPLITTER_TEST_URL", format!("{}/English.json", server.url))
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        }).collect();
+        for mut child in children {
+            assert!(child.wait().unwrap().success());
+        }
+
+        assert_eq!(server.downloads(), 1);
+        fs::remove_dir_all(&cache_directory).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+//! Minimal HTTP server standing in for the weight mirror in tests.
+
+use lazy_static::lazy_static;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+
+pub(crate) struct TestServer {
+    pub url: String,
+    downloads: Arc<AtomicUsize>,
+}
+
+impl TestServer {
+
+    /// Serves `files` (path, body) on a random local port until the test
+    /// process exits. Unknown paths get a 404.
+    pub fn serve(files: Vec<(&str, Vec<u8>)>) -> TestServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let files: HashMap<String, Vec<u8>> = files.into_iter().map(|(path, body)| (path.to_string(), body)).collect();
+        let downloads = Arc::new(AtomicUsize::new(0));
+        let counter = downloads.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue
+                };
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                // skip the headers
+                loop {
+                    let mut header = String::new();
+                    match reader.read_line(&mut header) {
+                        Ok(n) if n > 0 && !header.trim().is_empty() => continue,
+                        _ => break
+                    }
+                }
+
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or("");
+                let path = parts.next().unwrap_or("");
+
+                let response = match files.get(path) {
+                    Some(body) => {
+                        if method == "GET" {
+                            counter.fetch_add(1, Ordering::SeqCst);
+                        }
+                        let mut hasher = DefaultHasher::new();
+                        body.hash(&mut hasher);
+                        let mut response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: \"{:x}\"\r\nConnection: close\r\n\r\n",
+                            body.len(), hasher.finish()
+                        ).into_bytes();
+                        if method == "GET" {
+                            response.extend_from_slice(body);
+                        }
+                        response
+                    }
+                    None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+                };
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        TestServer { url, downloads }
+    }
+
+    /// Number of GET requests answered with a file.
+    pub fn downloads(&self) -> usize {
+        self.downloads.load(Ordering::SeqCst)
+    }
+}
+
+lazy_static! {
+    static ref REMOTE_TESTS: Mutex<()> = Mutex::new(());
+}
+
+/// Serializes tests touching the global download settings or the cache,
+/// which is a temporary directory in tests.
+pub(crate) fn remote_test_guard() -> MutexGuard<'static, ()> {
+    let guard = REMOTE_TESTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    crate::helper::resources::reset_settings();
+    guard
+}
@@ -0,0 +1,315 @@
+use crate::helper::common::Language;
+use crate::helper::detection::LanguageDetector;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::languages::get_custom_language;
+use crate::helper::resources::{weights_sha256, LocalResource, Resource};
+use crate::tokenize::profile::LanguageProfile;
+use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+
+/// Granularity of `AutoSentenceTokenizer::tokenize_regions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// Blocks of lines separated by blank lines.
+    Paragraph,
+    Line,
+}
+
+/// A sentence and the language of the model that found it. `start` and
+/// `end` are byte offsets into the tokenized text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedSentence {
+    pub language: Language,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Detects the language of each document and tokenizes it with the
+/// matching Punkt model. Models are loaded on first use and kept.
+pub struct AutoSentenceTokenizer {
+    detector: LanguageDetector,
+    fallback: Language,
+    weights_dir: Option<PathBuf>,
+    tokenizers: Mutex<HashMap<Language, Arc<PunktSentenceTokenizer>>>,
+}
+
+impl Default for AutoSentenceTokenizer {
+    fn default() -> Self {
+        AutoSentenceTokenizer {
+            detector: LanguageDetector::default(),
+            fallback: Language::English,
+            weights_dir: None,
+            tokenizers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl AutoSentenceTokenizer {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_detector(mut self, detector: LanguageDetector) -> Self {
+        self.detector = detector;
+        self
+    }
+
+    /// Language used when none is detected, English by default.
+    pub fn with_fallback(mut self, language: Language) -> Self {
+        self.fallback = language;
+        self
+    }
+
+    /// Reads `<Language>.json` weight files from `weights_dir` instead of
+    /// downloading them.
+    pub fn with_weights_dir(mut self, weights_dir: &str) -> Self {
+        self.weights_dir = Some(PathBuf::from(weights_dir));
+        self
+    }
+
+    pub fn detect(&self, text: &str) -> Language {
+        self.detector.detect(text).unwrap_or(self.fallback)
+    }
+
+    pub fn tokenize(&self, text: &str, realign_boundaries: bool) -> Result<Vec<String>, SentenceSplitterError> {
+        Ok(self.tokenize_with_language(text, realign_boundaries)?.1)
+    }
+
+    /// Also returns the language the text was tokenized as, the fallback
+    /// when the model of the detected language cannot be loaded.
+    pub fn tokenize_with_language(&self, text: &str, realign_boundaries: bool) -> Result<(Language, Vec<String>), SentenceSplitterError> {
+        let (language, tokenizer) = self._tokenizer_or_fallback(self.detect(text))?;
+        Ok((language, tokenizer.tokenize(text, realign_boundaries)))
+    }
+
+    /// For documents mixing languages: detects the language of every
+    /// paragraph or line and tokenizes it with the matching model. Adjacent
+    /// regions in the same language are tokenized together, and regions
+    /// without a detectable language keep the one before them. Regions whose
+    /// model cannot be loaded use the fallback.
+    pub fn tokenize_regions(&self, text: &str, realign_boundaries: bool, region: Region) -> Result<Vec<TaggedSentence>, SentenceSplitterError> {
+        let mut regions: Vec<(Language, usize, usize)> = Vec::new();
+
+        for (start, end) in _regions(text, region) {
+            let language = self.detector.detect(&text[start..end])
+                .or_else(|| regions.last().map(|(language, _, _)| *language))
+                .unwrap_or(self.fallback);
+
+            match regions.last_mut() {
+                Some((last_language, _, last_end)) if *last_language == language => *last_end = end,
+                _ => regions.push((language, start, end))
+            }
+        }
+
+        let mut sentences = Vec::new();
+        for (language, region_start, region_end) in regions {
+            let (language, tokenizer) = self._tokenizer_or_fallback(language)?;
+            for (start, end) in tokenizer.span_tokenize(&text[region_start..region_end], realign_boundaries) {
+                sentences.push(TaggedSentence {
+                    language,
+                    text: text[region_start + start..region_start + end].to_string(),
+                    start: region_start + start,
+                    end: region_start + end,
+                });
+            }
+        }
+        Ok(sentences)
+    }
+
+    /// The model for `language`, loading it on first use.
+    pub fn tokenizer(&self, language: Language) -> Result<Arc<PunktSentenceTokenizer>, SentenceSplitterError> {
+        if let Some(tokenizer) = self.tokenizers.lock().unwrap().get(&language) {
+            return Ok(tokenizer.clone());
+        }
+
+        let resource = match &self.weights_dir {
+            Some(weights_dir) => Resource::Local(LocalResource { local_path: weights_dir.join(format!("{}.json", language)) }),
+            None => PunktSentenceTokenizer::_language_weights(language)?
+        };
+        let weights = PunktSentenceTokenizer::_load_weights(&resource)?;
+        let tokenizer = Arc::new(PunktSentenceTokenizer::from_weights(weights, &LanguageProfile::for_language(language)));
+
+        Ok(self.tokenizers.lock().unwrap().entry(language).or_insert(tokenizer).clone())
+    }
+
+    /// The model for `language`, or the fallback model when no weights are
+    /// published for it. Weights that exist but cannot be loaded, e.g.
+    /// damaged or not cached in offline mode, are an error.
+    fn _tokenizer_or_fallback(&self, language: Language) -> Result<(Language, Arc<PunktSentenceTokenizer>), SentenceSplitterError> {
+        match self.tokenizer(language) {
+            Ok(tokenizer) => Ok((language, tokenizer)),
+            Err(_) if language != self.fallback && !_has_weights(language) => Ok((self.fallback, self.tokenizer(self.fallback)?)),
+            Err(error) => Err(error)
+        }
+    }
+}
+
+fn _has_weights(language: Language) -> bool {
+    weights_sha256(language).is_some() || get_custom_language(&language).is_some()
+}
+
+/// Byte ranges of the non-blank lines or paragraphs of `text`.
+fn _regions(text: &str, region: Region) -> Vec<(usize, usize)> {
+    let mut regions: Vec<(usize, usize)> = Vec::new();
+    let mut paragraph_break = true;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        if line.trim().is_empty() {
+            paragraph_break = true;
+            continue;
+        }
+        let end = start + line.trim_end().len();
+
+        match regions.last_mut() {
+            Some((_, last_end)) if region == Region::Paragraph && !paragraph_break => *last_end = end,
+            _ => regions.push((start, end))
+        }
+        paragraph_break = false;
+    }
+    regions
+}
+
+
+#[cfg(test)]
+mod auto_tokenizer_tests {
+    use super::*;
+
+    fn tokenizer() -> AutoSentenceTokenizer {
+        AutoSentenceTokenizer::new().with_weights_dir("data/weights")
+    }
+
+    #[test]
+    fn test_dispatch_to_detected_language() {
+        let tokenizer = tokenizer();
+
+        let (language, sentences) = tokenizer.tokenize_with_language(
+            "Der Vertrag wurde am 3. Mai unterschrieben. Danach ging er nach Hause.", true
+        ).unwrap();
+        assert_eq!(language, Language::German);
+        assert_eq!(sentences, vec!["Der Vertrag wurde am 3. Mai unterschrieben.", "Danach ging er nach Hause."]);
+
+        let (language, sentences) = tokenizer.tokenize_with_language(
+            "¿Dónde está la estación? Está cerca del parque.", true
+        ).unwrap();
+        assert_eq!(language, Language::Spanish);
+        assert_eq!(sentences, vec!["¿Dónde está la estación?", "Está cerca del parque."]);
+    }
+
+    #[test]
+    fn test_models_are_loaded_once() {
+        let tokenizer = tokenizer();
+        let first = tokenizer.tokenizer(Language::French).unwrap();
+        let second = tokenizer.tokenizer(Language::French).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_fallback_language() {
+        let tokenizer = tokenizer().with_fallback(Language::Italian);
+
+        assert_eq!(tokenizer.detect("1234."), Language::Italian);
+        assert_eq!(tokenizer.tokenize("1234.", true).unwrap(), vec!["1234."]);
+    }
+
+    #[test]
+    fn test_regions() {
+        let text = "One line.\nTwo\n\n  \nThree.\n";
+
+        assert_eq!(_regions(text, Region::Paragraph), vec![(0, 13), (18, 24)]);
+        assert_eq!(_regions(text, Region::Line), vec![(0, 9), (10, 13), (18, 24)]);
+    }
+
+    #[test]
+    fn test_mixed_language_paragraphs() {
+        let text = "The committee met on Monday. It approved the budget.\n\n\
+                    Der Vorsitzende sagte dazu Folgendes. Die Kosten seien zu hoch.\n\n\
+                    Le président a répondu aux questions. La séance est levée.";
+
+        let sentences = tokenizer().tokenize_regions(text, true, Region::Paragraph).unwrap();
+        let tagged: Vec<(Language, &str)> = sentences.iter().map(|s| (s.language, s.text.as_str())).collect();
+
+        assert_eq!(tagged, vec![
+            (Language::English, "The committee met on Monday."),
+            (Language::English, "It approved the budget."),
+            (Language::German, "Der Vorsitzende sagte dazu Folgendes."),
+            (Language::German, "Die Kosten seien zu hoch."),
+            (Language::French, "Le président a répondu aux questions."),
+            (Language::French, "La séance est levée."),
+        ]);
+        for sentence in sentences {
+            assert_eq!(&text[sentence.start..sentence.end], sentence.text);
+        }
+    }
+
+    #[test]
+    fn test_lines_in_the_same_language_are_joined() {
+        let text = "The meeting was held in\nthe town hall. Everyone came.\n42\nDas Treffen war gut.";
+
+        let sentences = tokenizer().tokenize_regions(text, true, Region::Line).unwrap();
+        let tagged: Vec<(Language, &str)> = sentences.iter().map(|s| (s.language, s.text.as_str())).collect();
+
+        assert_eq!(tagged, vec![
+            (Language::English, "The meeting was held in\nthe town hall."),
+            (Language::English, "Everyone came."),
+            (Language::English, "42"),
+            (Language::German, "Das Treffen war gut."),
+        ]);
+    }
+
+    #[test]
+    fn test_unsupported_scripts_use_the_fallback() {
+        let tokenizer = tokenizer();
+        let text = "Он пришёл домой. Она уже спала.";
+
+        let (language, sentences) = tokenizer.tokenize_with_language(text, true).unwrap();
+        assert_eq!(language, Language::English);
+        assert_eq!(sentences, vec!["Он пришёл домой.", "Она уже спала."]);
+
+        let regions = tokenizer.tokenize_regions(&format!("Der Hund schläft. Die Katze spielt.\n\n{}", text), true, Region::Paragraph).unwrap();
+        let tagged: Vec<(Language, &str)> = regions.iter().map(|s| (s.language, s.text.as_str())).collect();
+        assert_eq!(tagged, vec![
+            (Language::German, "Der Hund schläft."),
+            (Language::German, "Die Katze spielt."),
+            (Language::German, "Он пришёл домой."),
+            (Language::German, "Она уже спала."),
+        ]);
+    }
+
+    #[test]
+    fn test_load_errors_are_returned() {
+        let weights_dir = std::env::temp_dir().join(format!("sentence-splitter-{}-english-only", std::process::id()));
+        std::fs::create_dir_all(&weights_dir).unwrap();
+        std::fs::copy("data/weights/English.json", weights_dir.join("English.json")).unwrap();
+        let tokenizer = AutoSentenceTokenizer::new().with_weights_dir(weights_dir.to_str().unwrap());
+
+        assert!(matches!(
+            tokenizer.tokenize_with_language("Der Vertrag wurde gestern unterschrieben. Danach ging er nach Hause.", true),
+            Err(SentenceSplitterError::IOError(_))
+        ));
+        assert!(matches!(
+            tokenizer.tokenize_regions("The committee met on Monday.\n\nDer Vertrag wurde gestern unterschrieben.", true, Region::Paragraph),
+            Err(SentenceSplitterError::IOError(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_weights() {
+        let tokenizer = AutoSentenceTokenizer::new().with_weights_dir("does/not/exist");
+
+        assert!(matches!(
+            tokenizer.tokenize("The weather is nice today.", true),
+            Err(SentenceSplitterError::IOError(_))
+        ));
+    }
+}
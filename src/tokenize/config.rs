@@ -0,0 +1,266 @@
+use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::resources::{LocalResource, Resource};
+#[cfg(feature = "remote")]
+use crate::helper::resources::RemoteResource;
+use crate::tokenize::profile::{CaseFolding, LanguageProfile, OrthoHeuristic};
+use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+
+/// Serializable description of a `PunktSentenceTokenizer`.
+///
+/// ```json
+/// {
+///     "language": "de",
+///     "realign_boundaries": false,
+///     "profile": { "quote_aware": true },
+///     "abbreviations": ["bzw", "ggf"]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenizerConfig {
+    /// Selects the language profile, and the weights when `weights` is unset.
+    pub language: Option<Language>,
+    pub weights: Option<WeightSource>,
+    pub realign_boundaries: bool,
+    pub profile: ProfileOverrides,
+    /// Abbreviations (without the final period) added to the profile.
+    pub abbreviations: Vec<String>,
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        TokenizerConfig {
+            language: None,
+            weights: None,
+            realign_boundaries: true,
+            profile: ProfileOverrides::default(),
+            abbreviations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightSource {
+    Path(PathBuf),
+    #[cfg(feature = "remote")]
+    Url(String),
+}
+
+impl WeightSource {
+    pub fn to_resource(&self) -> Resource {
+        match self {
+            WeightSource::Path(path) => Resource::Local(LocalResource { local_path: path.clone() }),
+            #[cfg(feature = "remote")]
+            WeightSource::Url(url) => Resource::Remote(RemoteResource::new(url, "punkt/custom")),
+        }
+    }
+}
+
+/// Settings replacing the ones of the language profile. Unset fields keep
+/// the profile's value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileOverrides {
+    pub sent_end_chars: Option<Vec<String>>,
+    pub closing_punctuation: Option<String>,
+    pub unicode_closing_punctuation: Option<bool>,
+    pub quote_aware: Option<bool>,
+    pub quote_pairs: Option<Vec<(char, char)>>,
+    pub paired_marks: Option<Vec<(char, char)>>,
+    pub opening_punctuation: Option<String>,
+    pub case_folding: Option<CaseFolding>,
+    pub ordinal_numbers: Option<bool>,
+    pub month_names: Option<Vec<String>>,
+    pub ortho_heuristic: Option<OrthoHeuristic>,
+    pub cjk: Option<bool>,
+}
+
+impl ProfileOverrides {
+    pub fn apply(&self, mut profile: LanguageProfile) -> LanguageProfile {
+        if let Some(sent_end_chars) = &self.sent_end_chars {
+            profile.sent_end_chars = sent_end_chars.clone();
+        }
+        if let Some(closing_punctuation) = &self.closing_punctuation {
+            profile.closing_punctuation = closing_punctuation.clone();
+        }
+        if let Some(unicode_closing_punctuation) = self.unicode_closing_punctuation {
+            profile.unicode_closing_punctuation = unicode_closing_punctuation;
+        }
+        if let Some(quote_aware) = self.quote_aware {
+            profile.quote_aware = quote_aware;
+        }
+        if let Some(quote_pairs) = &self.quote_pairs {
+            profile.quote_pairs = quote_pairs.clone();
+        }
+        if let Some(paired_marks) = &self.paired_marks {
+            profile.paired_marks = paired_marks.clone();
+        }
+        if let Some(opening_punctuation) = &self.opening_punctuation {
+            profile.opening_punctuation = opening_punctuation.clone();
+        }
+        if let Some(case_folding) = self.case_folding {
+            profile.case_folding = case_folding;
+        }
+        if let Some(ordinal_numbers) = self.ordinal_numbers {
+            profile.ordinal_numbers = ordinal_numbers;
+        }
+        if let Some(month_names) = &self.month_names {
+            profile.month_names = month_names.clone();
+        }
+        if let Some(ortho_heuristic) = self.ortho_heuristic {
+            profile.ortho_heuristic = ortho_heuristic;
+        }
+        if let Some(cjk) = self.cjk {
+            profile.cjk = cjk;
+        }
+        profile
+    }
+}
+
+impl TokenizerConfig {
+
+    /// The language profile with the overrides and extra abbreviations applied.
+    pub fn language_profile(&self) -> LanguageProfile {
+        let profile = match self.language {
+            Some(language) => LanguageProfile::for_language(language),
+            None => LanguageProfile::default()
+        };
+        let mut profile = self.profile.apply(profile);
+        profile.abbreviations.extend(self.abbreviations.iter().cloned());
+        profile
+    }
+
+    pub fn build(&self) -> Result<PunktSentenceTokenizer, SentenceSplitterError> {
+        let resource = match (&self.weights, self.language) {
+            (Some(weights), _) => weights.to_resource(),
+            (None, Some(language)) => PunktSentenceTokenizer::_language_weights(language)?,
+            (None, None) => return Err(SentenceSplitterError::ConfigError(
+                String::from("either `language` or `weights` must be set")
+            )),
+        };
+        let weights = PunktSentenceTokenizer::_load_weights(&resource)?;
+
+        Ok(PunktSentenceTokenizer::from_weights(weights, &self.language_profile())
+            .with_realign_boundaries(self.realign_boundaries))
+    }
+}
+
+impl TryFrom<TokenizerConfig> for PunktSentenceTokenizer {
+    type Error = SentenceSplitterError;
+
+    fn try_from(config: TokenizerConfig) -> Result<Self, Self::Error> {
+        config.build()
+    }
+}
+
+impl TryFrom<&TokenizerConfig> for PunktSentenceTokenizer {
+    type Error = SentenceSplitterError;
+
+    fn try_from(config: &TokenizerConfig) -> Result<Self, Self::Error> {
+        config.build()
+    }
+}
+
+
+#[cfg(test)]
+mod tokenizer_config_tests {
+    use super::*;
+
+    fn english_config() -> TokenizerConfig {
+        TokenizerConfig {
+            language: Some(Language::English),
+            weights: Some(WeightSource::Path(PathBuf::from("data/weights/English.json"))),
+            ..TokenizerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_deserialize_with_defaults() {
+        let config: TokenizerConfig = serde_json::from_str(r#"{
+            "language": "pt-BR",
+            "weights": { "path": "data/weights/Portuguese.json" },
+            "profile": { "quote_aware": true, "case_folding": "turkic" }
+        }"#).unwrap();
+
+        assert_eq!(config.language, Some(Language::Portuguese));
+        assert_eq!(config.weights, Some(WeightSource::Path(PathBuf::from("data/weights/Portuguese.json"))));
+        assert!(config.realign_boundaries);
+        assert_eq!(config.profile.quote_aware, Some(true));
+        assert_eq!(config.profile.case_folding, Some(CaseFolding::Turkic));
+        assert!(config.abbreviations.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config = TokenizerConfig {
+            abbreviations: vec![String::from("approx")],
+            profile: ProfileOverrides { quote_pairs: Some(vec![('„', '“')]), ..ProfileOverrides::default() },
+            ..english_config()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert!(json.contains(r#""language":"English""#));
+        assert_eq!(serde_json::from_str::<TokenizerConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_unknown_language_is_rejected() {
+        let error = serde_json::from_str::<TokenizerConfig>(r#"{"language": "Klingon"}"#).unwrap_err();
+        assert!(error.to_string().contains("Unknown language `Klingon`"));
+    }
+
+    #[test]
+    fn test_overrides_and_abbreviations() {
+        let config = TokenizerConfig {
+            profile: ProfileOverrides { cjk: Some(true), ..ProfileOverrides::default() },
+            abbreviations: vec![String::from("approx")],
+            ..english_config()
+        };
+        let profile = config.language_profile();
+
+        assert!(profile.cjk);
+        assert!(!profile.quote_aware);
+        assert!(profile.abbreviations.contains(&String::from("approx")));
+
+        let text = "It weighs approx. five kilos.";
+        assert_eq!(
+            PunktSentenceTokenizer::try_from(&config).unwrap().sentences(text),
+            vec!["It weighs approx. five kilos."]
+        );
+        assert_eq!(
+            PunktSentenceTokenizer::try_from(english_config()).unwrap().sentences(text),
+            vec!["It weighs approx.", "five kilos."]
+        );
+    }
+
+    #[test]
+    fn test_realign_boundaries() {
+        let text = "\"Stop.\" He left.";
+        let realigned = PunktSentenceTokenizer::try_from(english_config()).unwrap();
+        let not_realigned = PunktSentenceTokenizer::try_from(
+            TokenizerConfig { realign_boundaries: false, ..english_config() }
+        ).unwrap();
+
+        assert_eq!(realigned.sentences(text), realigned.tokenize(text, true));
+        assert_eq!(not_realigned.sentences(text), not_realigned.tokenize(text, false));
+    }
+
+    #[test]
+    fn test_missing_weights() {
+        assert!(matches!(
+            TokenizerConfig::default().build(),
+            Err(SentenceSplitterError::ConfigError(_))
+        ));
+        assert!(matches!(
+            TokenizerConfig { weights: Some(WeightSource::Path(PathBuf::from("missing.json"))), ..english_config() }.build(),
+            Err(SentenceSplitterError::IOError(_))
+        ));
+    }
+}
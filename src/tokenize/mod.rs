@@ -1 +1,7 @@
-pub mod punkt;
\ No newline at end of file
+pub mod punkt;
+pub mod profile;
+pub mod auto;
+pub mod registry;
+pub mod reload;
+#[cfg(feature = "serde")]
+pub mod config;
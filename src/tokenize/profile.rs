@@ -0,0 +1,269 @@
+use crate::helper::common::Language;
+use crate::helper::languages::get_custom_language;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum CaseFolding {
+    Default,
+    /// Turkish and Azerbaijani, where `İ` lowercases to `i` and `I` to `ı`.
+    Turkic,
+}
+
+impl CaseFolding {
+    pub fn to_lowercase(&self, s: &str) -> String {
+        match self {
+            CaseFolding::Default => s.to_lowercase(),
+            CaseFolding::Turkic => s.chars().map(|c| match c {
+                'İ' => String::from("i"),
+                'I' => String::from("ı"),
+                _ => c.to_lowercase().collect()
+            }).collect()
+        }
+    }
+
+    /// Titlecase digraphs such as `ǅ` count as uppercase.
+    pub fn is_uppercase(&self, c: char) -> bool {
+        c.is_uppercase() || (!c.is_lowercase() && c.to_lowercase().next() != Some(c))
+    }
+
+    pub fn is_lowercase(&self, c: char) -> bool {
+        c.is_lowercase()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum OrthoHeuristic {
+    /// Punkt's heuristic on the letter case of the next word.
+    Case,
+    /// For words without letter case (Devanagari, Arabic, Thai, Hebrew,
+    /// Georgian, ...) decides from the learned sentence starters and
+    /// collocations; cased words still use `Case`.
+    Statistics,
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageProfile {
+    pub sent_end_chars: Vec<String>,
+    pub closing_punctuation: String,
+    pub unicode_closing_punctuation: bool,
+    pub quote_pairs: Vec<(char, char)>,
+    pub quote_aware: bool,
+    pub paired_marks: Vec<(char, char)>,
+    pub opening_punctuation: String,
+    pub case_folding: CaseFolding,
+    pub ordinal_numbers: bool,
+    pub month_names: Vec<String>,
+    pub abbreviations: Vec<String>,
+    pub ortho_heuristic: OrthoHeuristic,
+    pub cjk: bool,
+}
+
+impl Default for LanguageProfile {
+    fn default() -> Self {
+        LanguageProfile {
+            sent_end_chars: [".", "?", "!", "‼", "⁇", "⁈", "⁉"].iter().map(|x| x.to_string()).collect(),
+            closing_punctuation: String::from("\"')]}"),
+            unicode_closing_punctuation: true,
+            quote_pairs: vec![('"', '"'), ('“', '”'), ('‘', '’'), ('«', '»'), ('「', '」'), ('『', '』')],
+            quote_aware: false,
+            paired_marks: Vec::new(),
+            opening_punctuation: String::new(),
+            case_folding: CaseFolding::Default,
+            ordinal_numbers: false,
+            month_names: Vec::new(),
+            abbreviations: Vec::new(),
+            ortho_heuristic: OrthoHeuristic::Case,
+            cjk: false,
+        }
+    }
+}
+
+impl LanguageProfile {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Profile used when a tokenizer is created for `language`.
+    pub fn for_language(language: Language) -> Self {
+        let profile = Self::default();
+        match language {
+            Language::Czech | Language::Slovenian => _low_high_quotes(profile),
+            Language::German => _low_high_quotes(profile)
+                .ordinal_numbers(true)
+                .month_names(GERMAN_MONTH_NAMES)
+                .add_month_names(GERMAN_MONTH_ABBREVIATIONS)
+                .add_abbreviations(GERMAN_MONTH_ABBREVIATIONS),
+            Language::Danish => profile
+                .add_closing_punctuation("«‹")
+                .add_quote_pairs(&[('»', '«')]),
+            // `«Bonjour` is the word `Bonjour`, whether or not a (no-break)
+            // space follows the guillemet.
+            Language::French => profile.opening_punctuation("«‹"),
+            Language::Polish => profile.add_quote_pairs(&[('„', '”')]),
+            Language::Turkish => profile.case_folding(CaseFolding::Turkic),
+            Language::Spanish => profile.paired_marks(&[('¿', '?'), ('¡', '!'), ('¿', '!'), ('¡', '?')]),
+            Language::Custom(_) => get_custom_language(&language).map_or(profile, |language| language.profile),
+            _ => profile
+        }
+    }
+
+    /// Enables segmentation of Chinese and Japanese text, where full-width
+    /// terminators such as `。` are not followed by whitespace.
+    pub fn cjk(mut self, enabled: bool) -> Self {
+        self.cjk = enabled;
+        self
+    }
+
+    /// Characters moved back to the end of the previous sentence when
+    /// boundaries are realigned.
+    pub fn closing_punctuation(mut self, chars: &str) -> Self {
+        self.closing_punctuation = chars.to_string();
+        self
+    }
+
+    pub fn add_closing_punctuation(mut self, chars: &str) -> Self {
+        for c in chars.chars() {
+            if !self.closing_punctuation.contains(c) {
+                self.closing_punctuation.push(c)
+            }
+        }
+        self
+    }
+
+    /// Also treats every closing bracket and final quote (Unicode general
+    /// categories Pe and Pf) as closing punctuation.
+    pub fn unicode_closing_punctuation(mut self, enabled: bool) -> Self {
+        self.unicode_closing_punctuation = enabled;
+        self
+    }
+
+    /// Keeps dialogue such as `"Stop!" she said.` in one sentence: a boundary
+    /// inside a quotation is dropped when the text after the closing quote
+    /// continues in lowercase.
+    pub fn quote_aware(mut self, enabled: bool) -> Self {
+        self.quote_aware = enabled;
+        self
+    }
+
+    /// Opening and closing quotation marks tracked in quote-aware mode.
+    pub fn quote_pairs(mut self, quote_pairs: &[(char, char)]) -> Self {
+        self.quote_pairs = quote_pairs.to_vec();
+        self
+    }
+
+    pub fn add_quote_pairs(mut self, quote_pairs: &[(char, char)]) -> Self {
+        for pair in quote_pairs {
+            if !self.quote_pairs.contains(pair) {
+                self.quote_pairs.push(*pair)
+            }
+        }
+        self
+    }
+
+    /// Inverted marks such as Spanish `¿…?` and `¡…!`. The opening mark
+    /// starts a new sentence and no boundary is placed inside an open pair.
+    pub fn paired_marks(mut self, paired_marks: &[(char, char)]) -> Self {
+        self.paired_marks = paired_marks.to_vec();
+        self
+    }
+
+    /// Characters split off the start of a word instead of being part of it.
+    pub fn opening_punctuation(mut self, chars: &str) -> Self {
+        self.opening_punctuation = chars.to_string();
+        self
+    }
+
+    pub fn case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Treats `3.` and `II.` as ordinals rather than sentence ends when the
+    /// next word is a month, lowercase or a noun, and keeps `1. … 2. …`
+    /// enumerations together.
+    pub fn ordinal_numbers(mut self, enabled: bool) -> Self {
+        self.ordinal_numbers = enabled;
+        self
+    }
+
+    /// Month names and abbreviations that follow an ordinal in dates.
+    pub fn month_names(mut self, month_names: &[&str]) -> Self {
+        self.month_names = month_names.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    pub fn add_month_names(mut self, month_names: &[&str]) -> Self {
+        self.month_names.extend(month_names.iter().map(|x| x.to_string()));
+        self
+    }
+
+    /// Abbreviations (without the final period) recognised in addition to
+    /// the ones learned in the weights.
+    pub fn abbreviations(mut self, abbreviations: &[&str]) -> Self {
+        self.abbreviations = abbreviations.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    pub fn add_abbreviations(mut self, abbreviations: &[&str]) -> Self {
+        self.abbreviations.extend(abbreviations.iter().map(|x| x.to_string()));
+        self
+    }
+
+    pub fn ortho_heuristic(mut self, ortho_heuristic: OrthoHeuristic) -> Self {
+        self.ortho_heuristic = ortho_heuristic;
+        self
+    }
+
+    pub fn sent_end_chars(mut self, sent_end_chars: &[&str]) -> Self {
+        self.sent_end_chars = sent_end_chars.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    pub(crate) fn effective_sent_end_chars(&self) -> Vec<String> {
+        let mut chars = self.sent_end_chars.clone();
+        if self.cjk {
+            for c in CJK_SENT_END_CHARS.chars().map(String::from) {
+                if !chars.contains(&c) {
+                    chars.push(c)
+                }
+            }
+        }
+        chars
+    }
+
+    /// Character class of the closing punctuation. Without any it is a
+    /// class that never matches, `[]` is not a valid pattern.
+    pub(crate) fn re_closing_chars(&self) -> String {
+        if !self.unicode_closing_punctuation && self.closing_punctuation.is_empty() {
+            return String::from(r"[^\s\S]");
+        }
+        format!(
+            "[{unicode}{escaped_string}]",
+            unicode=if self.unicode_closing_punctuation { r"\p{Pe}\p{Pf}" } else { "" },
+            escaped_string=fancy_regex::escape(&self.closing_punctuation)
+        )
+    }
+}
+
+/// „quoted“ and »quoted« styles, which close with characters Unicode
+/// classifies as opening quotes.
+fn _low_high_quotes(profile: LanguageProfile) -> LanguageProfile {
+    profile
+        .add_closing_punctuation("“‘«‹")
+        .add_quote_pairs(&[('„', '“'), ('‚', '‘'), ('»', '«')])
+}
+
+pub(crate) static CJK_SENT_END_CHARS: &str = "。｡！？";
+
+static GERMAN_MONTH_NAMES: &[&str] = &[
+    "januar", "jänner", "februar", "feber", "märz", "april", "mai", "juni", "juli",
+    "august", "september", "oktober", "november", "dezember",
+];
+
+static GERMAN_MONTH_ABBREVIATIONS: &[&str] = &[
+    "jan", "feb", "mär", "mrz", "apr", "jun", "jul", "aug", "sep", "sept", "okt", "nov", "dez",
+];
@@ -1,9 +1,15 @@
 use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::languages::get_custom_language;
 use crate::helper::slice::StringSlice;
-use crate::helper::resources::{Resource, RemoteResource};
+use crate::helper::resources::Resource;
+#[cfg(feature = "remote")]
+use crate::helper::resources::{RemoteResource, weights_sha256, weights_url};
+use crate::tokenize::profile::{LanguageProfile, CaseFolding, OrthoHeuristic, CJK_SENT_END_CHARS};
 
 use std::{fs};
 use fancy_regex::*;
+use lazy_static::lazy_static;
 use std::ops::{BitOr};
 use serde::{Serialize, Deserialize};
 use std::collections::{HashMap, HashSet};
@@ -16,9 +22,17 @@ static _ORTHO_BEG_LC: usize = 1 << 4;
 static _ORTHO_MID_LC: usize = 1 << 5;
 static _ORTHO_UNK_LC: usize = 1 << 6;
 
+static _ELLIPSIS: &str = "…";
+
 static _ORTHO_UC: usize = _ORTHO_BEG_UC + _ORTHO_MID_UC + _ORTHO_UNK_UC;
 static _ORTHO_LC: usize = _ORTHO_BEG_LC + _ORTHO_MID_LC + _ORTHO_UNK_LC;
 
+lazy_static! {
+    static ref _RE_ROMAN_NUMERAL: Regex = Regex::new(r"^([IVXLCDM]+\.$)").unwrap();
+    /// `1.` to `999.` as a word.
+    static ref _RE_ENUMERATION_MARKER: Regex = Regex::new(r"(?<!\S)(\d{1,3})\.(?=\s)").unwrap();
+}
+
 
 #[derive(Debug, Clone, Copy)]
 pub struct Slice {
@@ -103,34 +117,54 @@ impl PunktParameters {
 pub struct PunktLanguagePros {
     _word_tokenizer_re: Regex,
     period_context_re: Regex,
+    cjk_boundary_re: Option<Regex>,
 }
 
 impl PunktLanguagePros {
-    fn new(
-        word_tokenize_fmt: &str,
-        re_non_word_chars: &str,
-        re_multi_char_punct: &str,
-        re_word_start: &str,
-        period_context_fmt: &str,
-        re_sent_end_chars: &str,
-    ) -> Self {
+    fn new(static_vars: &PunktLanguageStaticVars) -> Self {
         PunktLanguagePros {
-            _word_tokenizer_re: Self::build_word_tokenizer_re(word_tokenize_fmt, re_non_word_chars, re_multi_char_punct, re_word_start),
-            period_context_re: Self::build_period_context_re(period_context_fmt, re_sent_end_chars, re_non_word_chars),
+            _word_tokenizer_re: Self::build_word_tokenizer_re(
+                static_vars._word_tokenize_fmt,
+                &static_vars._re_non_word_chars,
+                static_vars._re_multi_char_punct,
+                &static_vars._re_word_start,
+                &static_vars._re_closing_chars
+            ),
+            period_context_re: Self::build_period_context_re(
+                static_vars._period_context_fmt,
+                &static_vars._re_sent_end_chars,
+                &static_vars._re_non_word_chars,
+                &static_vars._re_closing_chars
+            ),
+            cjk_boundary_re: if static_vars.cjk { Some(Self::build_cjk_boundary_re(&static_vars._re_closing_chars)) } else { None },
         }
     }
 
+    /// Full-width terminators are never abbreviations, so a boundary follows
+    /// them (and any closing brackets) even without intervening whitespace.
+    fn build_cjk_boundary_re(re_closing_chars: &str) -> Regex {
+        fancy_regex::Regex::new(
+            &format!(
+                r#"[{end}]+{close}*(?=\s*\S)"#,
+                end=fancy_regex::escape(CJK_SENT_END_CHARS),
+                close=re_closing_chars
+            )
+        ).unwrap()
+    }
+
     fn build_word_tokenizer_re(
         word_tokenize_fmt: &str,
         re_non_word_chars: &str,
         re_multi_char_punct: &str,
-        re_word_start: &str
+        re_word_start: &str,
+        re_closing_chars: &str,
     ) -> Regex {
         fancy_regex::Regex::new(
          &word_tokenize_fmt
             .replace("{NonWord}", re_non_word_chars)
             .replace("{MultiChar}", re_multi_char_punct)
             .replace("{WordStart}", re_word_start)
+            .replace("{Closing}", re_closing_chars)
         ).unwrap()
     }
 
@@ -138,76 +172,103 @@ impl PunktLanguagePros {
         period_context_fmt: &str,
         re_sent_end_chars: &str,
         re_non_word_chars: &str,
+        re_closing_chars: &str,
     ) -> fancy_regex::Regex {
         fancy_regex::Regex::new(
         &period_context_fmt
             .replace("{SentEndChars}", re_sent_end_chars)
             .replace("{NonWord}", re_non_word_chars)
+            .replace("{Closing}", re_closing_chars)
         ).unwrap()
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct PunktLanguageStaticVars<'a> {
+pub struct PunktLanguageStaticVars {
     _re_sent_end_chars: String,
     _re_non_word_chars: String,
-    sent_end_chars: HashSet<&'a str>,
+    _re_closing_chars: String,
+    sent_end_chars: HashSet<String>,
+    quote_pairs: Option<Vec<(char, char)>>,
+    paired_marks: Vec<(char, char)>,
+    case_folding: CaseFolding,
+    ordinal_numbers: bool,
+    month_names: HashSet<String>,
+    abbreviations: HashSet<String>,
+    ortho_heuristic: OrthoHeuristic,
+    cjk: bool,
     internal_punctuation: &'static str,
     re_boundary_realignment: Regex,
-    _re_word_start: &'static str,
+    _re_word_start: String,
     _re_multi_char_punct: &'static str,
     _word_tokenize_fmt: &'static str,
     _period_context_fmt: &'static str,
 }
 
-impl <'a>PunktLanguageStaticVars<'a> {
-    fn new(sent_end_chars: &HashSet<&'a str>) -> Self {
+impl PunktLanguageStaticVars {
+    fn new(profile: &LanguageProfile) -> Self {
+        let sent_end_chars: HashSet<String> = profile.effective_sent_end_chars().into_iter().collect();
+        let opening_marks: String = profile.paired_marks.iter()
+            .map(|(opening, _)| *opening)
+            .chain(profile.opening_punctuation.chars())
+            .collect();
+        let re_closing_chars = profile.re_closing_chars();
+
         PunktLanguageStaticVars {
-            _re_sent_end_chars: Self::build_re_sent_end_chars(sent_end_chars),
-            _re_non_word_chars: Self::build_re_non_word_chars(sent_end_chars),
-            sent_end_chars: sent_end_chars.clone(),
+            _re_sent_end_chars: Self::build_re_sent_end_chars(&sent_end_chars),
+            _re_non_word_chars: Self::build_re_non_word_chars(&sent_end_chars, &opening_marks),
+            re_boundary_realignment: Regex::new(&format!(r#"^{}+?(?:\s+|(?=--)|$)"#, re_closing_chars)).unwrap(),
+            _re_closing_chars: re_closing_chars,
+            sent_end_chars,
+            quote_pairs: if profile.quote_aware { Some(profile.quote_pairs.clone()) } else { None },
+            paired_marks: profile.paired_marks.clone(),
+            case_folding: profile.case_folding,
+            ordinal_numbers: profile.ordinal_numbers,
+            month_names: profile.month_names.iter().map(|x| profile.case_folding.to_lowercase(x)).collect(),
+            abbreviations: profile.abbreviations.iter().map(|x| profile.case_folding.to_lowercase(x)).collect(),
+            ortho_heuristic: profile.ortho_heuristic,
+            cjk: profile.cjk,
             internal_punctuation: ",:;",
-            re_boundary_realignment: Regex::new(r#"^["\')\]}]+?(?:\s+|(?=--)|$)"#).unwrap(),
-            _re_word_start: r#"[^\(\"\`{\[:;&\#\*@\)}\]\-,]"#,
-            _re_multi_char_punct: r#"(?:\-{2,}|\.{2,}|(?:\.\s){2,}\.)"#,
-            _word_tokenize_fmt: r#"(?x) ({MultiChar} | (?={WordStart})\S+?(?=\s|$| {NonWord} | {MultiChar} | ,(?=$|\s|{NonWord}| {MultiChar})) | \S)"#,
-            _period_context_fmt: r#"(?x) \S* {SentEndChars} (?=(?P<after_tok> {NonWord} | \s+ (?P<next_tok> \S+ )))"#,
+            _re_word_start: String::from(r#"[^\(\"\`{\[:;&\#\*@\)}\]\-,{string}]"#).replace(
+                "{string}",
+                &fancy_regex::escape(&opening_marks)
+            ),
+            _re_multi_char_punct: r#"(?:\-{2,}|[\.…]{2,}|(?:\.\s){2,}\.)"#,
+            _word_tokenize_fmt: r#"(?x) ({MultiChar} | (?={WordStart})\S+?(?=\s|$| {NonWord} | {Closing}+(?=\s|$) | {MultiChar} | ,(?=$|\s|{NonWord}| {MultiChar})) | \S)"#,
+            _period_context_fmt: r#"(?x) \S* {SentEndChars} (?=(?P<after_tok> {NonWord} | {Closing}+(?=\s|$) | \s+ (?P<next_tok> \S+ )))"#,
         }
     }
 
-    fn build_re_sent_end_chars(sent_end_chars: &HashSet<&str>) -> String {
+    /// Includes `…`, which is then annotated as an ellipsis like `...`.
+    fn build_re_sent_end_chars(sent_end_chars: &HashSet<String>) -> String {
         format!(
             "[{escaped_string}]",
-            escaped_string=fancy_regex::escape(&sent_end_chars.into_iter().map(|x|*x).collect::<Vec<&str>>().join(""))
+            escaped_string=fancy_regex::escape(&(sent_end_chars.iter().map(|x|x.as_str()).collect::<Vec<&str>>().join("") + _ELLIPSIS))
         )
     }
 
-    fn build_re_non_word_chars(sent_end_chars: &HashSet<&str>) -> String {
+    fn build_re_non_word_chars(sent_end_chars: &HashSet<String>, opening_marks: &str) -> String {
         String::from(r#"(?:[;)}"\]*:@'({\[{string}])"#).replace(
             "{string}",
-            &fancy_regex::escape(&sent_end_chars.into_iter().map(|x|*x).collect::<Vec<&str>>().join("").replace(".", ""))
+            &fancy_regex::escape(&(sent_end_chars.iter().map(|x|x.as_str()).collect::<Vec<&str>>().join("").replace(".", "") + _ELLIPSIS + opening_marks))
         )
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct PunktLanguageVars<'a> {
-    punkt_language_static_vars: PunktLanguageStaticVars<'a>,
+pub struct PunktLanguageVars {
+    punkt_language_static_vars: PunktLanguageStaticVars,
     punkt_language_pros: PunktLanguagePros,
 }
 
-impl <'a>PunktLanguageVars<'a> {
+impl PunktLanguageVars {
     fn new() -> Self {
-        let sec = HashSet::from([".", "?", "!"]);
-        let punkt_language_static_vars = PunktLanguageStaticVars::new(&sec);
-        let punkt_language_pros = PunktLanguagePros::new(
-                &punkt_language_static_vars._word_tokenize_fmt,
-                &punkt_language_static_vars._re_non_word_chars,
-                &punkt_language_static_vars._re_multi_char_punct,
-                &punkt_language_static_vars._re_word_start,
-                &punkt_language_static_vars._period_context_fmt,
-                &punkt_language_static_vars._re_sent_end_chars,
-        );
+        Self::from_profile(&LanguageProfile::default())
+    }
+
+    fn from_profile(profile: &LanguageProfile) -> Self {
+        let punkt_language_static_vars = PunktLanguageStaticVars::new(profile);
+        let punkt_language_pros = PunktLanguagePros::new(&punkt_language_static_vars);
         Self {
             punkt_language_static_vars,
             punkt_language_pros
@@ -234,27 +295,29 @@ pub struct PunktToken {
     line_start: Option<bool>,
     sent_break: Option<bool>,
     abbr: Option<bool>,
-    ellipsis: Option<bool>
+    ellipsis: Option<bool>,
+    case_folding: CaseFolding
 }
 
 impl PunktToken {
-    fn new(token: &str, para_start: bool, line_start: bool) -> Self {
+    fn new(token: &str, para_start: bool, line_start: bool, case_folding: CaseFolding) -> Self {
         Self {
             token: token.to_string(),
-            type_: Self::_get_type(token),
-            period_final: token.ends_with("."),
+            type_: Self::_get_type(token, case_folding),
+            period_final: token.ends_with('.') || token.ends_with(_ELLIPSIS),
             para_start: Some(para_start),
             line_start: Some(line_start),
             sent_break: None,
             abbr: None,
-            ellipsis: None
+            ellipsis: None,
+            case_folding
         }
     }
 
-    fn _get_type(tok: &str) -> String {
+    fn _get_type(tok: &str, case_folding: CaseFolding) -> String {
         fancy_regex::Regex::new(r"(?xm) ^-?[\.,]?\d[\d,\.-]*\.?$")
             .unwrap()
-            .replace(&tok.to_lowercase(), "##number##")
+            .replace(&case_folding.to_lowercase(tok), "##number##")
             .to_string()
     }
 
@@ -273,11 +336,15 @@ impl PunktToken {
     }
 
     fn first_upper(&self) -> bool {
-        self.token.chars().nth(0).unwrap().is_uppercase()
+        self.case_folding.is_uppercase(self.token.chars().nth(0).unwrap())
     }
 
     fn first_lower(&self) -> bool {
-        self.token.chars().nth(0).unwrap().is_lowercase()
+        self.case_folding.is_lowercase(self.token.chars().nth(0).unwrap())
+    }
+
+    fn first_cased(&self) -> bool {
+        self.first_upper() || self.first_lower()
     }
 
     #[allow(dead_code)]
@@ -291,7 +358,7 @@ impl PunktToken {
     }
 
     fn is_ellipsis(&self) -> bool {
-        fancy_regex::Regex::new(r"^(\.\.+$)")
+        fancy_regex::Regex::new(r"^(\.\.+$|[\.…]*…[\.…]*$)")
             .unwrap()
             .find(&self.token).unwrap().is_some()
     }
@@ -306,20 +373,29 @@ impl PunktToken {
             .unwrap()
             .find(&self.token).unwrap().is_some()
     }
+
+    fn is_roman_numeral(&self) -> bool {
+        _RE_ROMAN_NUMERAL.find(&self.token).unwrap().is_some()
+    }
 }
 
 #[derive(Debug)]
-pub struct PunktBaseClass<'a> {
-    lang_vars: PunktLanguageVars<'a>,
-    params: PunktParameters
+pub struct PunktBaseClass {
+    lang_vars: PunktLanguageVars,
+    params: PunktParameters,
+    /// Second words of the collocations, looked up by the statistics
+    /// heuristic.
+    collocation_followers: HashSet<String>,
 }
 
-impl <'a>PunktBaseClass<'a> {
+impl PunktBaseClass {
 
-    fn new(lang_vars: Option<PunktLanguageVars<'a>>, params: Option<PunktParameters>) -> Self {
+    fn new(lang_vars: Option<PunktLanguageVars>, params: Option<PunktParameters>) -> Self {
+        let params = params.unwrap_or(PunktParameters::new());
         Self {
             lang_vars: lang_vars.unwrap_or(PunktLanguageVars::new()),
-            params: params.unwrap_or(PunktParameters::new())
+            collocation_followers: params.collocations.iter().map(|c| c.1.clone()).collect(),
+            params
         }
     }
 
@@ -342,7 +418,8 @@ impl <'a>PunktBaseClass<'a> {
                     PunktToken::new(
                         &tok.unwrap(),
                         parastart,
-                        true
+                        true,
+                        self.lang_vars.punkt_language_static_vars.case_folding
                     )
                 );
 
@@ -353,7 +430,8 @@ impl <'a>PunktBaseClass<'a> {
                         PunktToken::new(
                             &t,
                             parastart,
-                            true
+                            true,
+                            self.lang_vars.punkt_language_static_vars.case_folding
                         )
                     );
                 }
@@ -377,11 +455,14 @@ impl <'a>PunktBaseClass<'a> {
             aug_tok.ellipsis = Some(true)
         } else if aug_tok.period_final && !token.ends_with("..") {
 
-            let lower_token = token[0..token.len()-1].to_lowercase();
+            let without_period = token.strip_suffix('.').or_else(|| token.strip_suffix(_ELLIPSIS)).unwrap_or(token);
+            let lower_token = self.lang_vars.punkt_language_static_vars.case_folding.to_lowercase(without_period);
 
             if self.params.abbrev_types.contains(&lower_token)
                 ||
-                self.params.abbrev_types.contains(lower_token.split("-").last().unwrap()) {
+                self.params.abbrev_types.contains(lower_token.split("-").last().unwrap())
+                ||
+                self.lang_vars.punkt_language_static_vars.abbreviations.contains(&lower_token) {
                 aug_tok.abbr = Some(true)
             } else {
                 aug_tok.sent_break = Some(true)
@@ -392,37 +473,108 @@ impl <'a>PunktBaseClass<'a> {
 
 
 #[derive(Debug)]
-pub struct PunktSentenceTokenizer<'a> {
-    punkt_base_class: PunktBaseClass<'a>,
+pub struct PunktSentenceTokenizer {
+    punkt_base_class: PunktBaseClass,
+    realign_boundaries: bool,
 }
 
-impl PunktSentenceTokenizer<'_> {
+impl PunktSentenceTokenizer {
 
     pub fn new(weight_file_path: Option<&str>, language: Option<Language>) -> Self {
 
-        let json_file_string = match weight_file_path {
-            Some(path) => fs::read_to_string(path).expect("Unable to read weight file."),
+        let weights: PunktParameters = match weight_file_path {
+            Some(path) => {
+                let json_file_string = fs::read_to_string(path).expect("Unable to read weight file.");
+                serde_json::from_str(&json_file_string).expect("Unable to parse weight file.")
+            }
             None => {
-                let config_resource = Resource::Remote(RemoteResource::new(
-                    format!("https://raw.githubusercontent.com/Kavan72/sentence-splitter/master/data/weights/{}.json", language.unwrap().to_string()).as_str(),
-                    format!("punkt/{}", language.unwrap().to_string()).as_str(),
-                ));
-                fs::read_to_string(config_resource.get_local_path().unwrap()).expect("Unable to read weight file.")
+                let language = language.expect("Either a weight file or a language is required.");
+                Self::_language_weights(language)
+                    .and_then(|resource| Self::_load_weights(&resource))
+                    .unwrap_or_else(|error| panic!("Unable to load the weights of {}: {}", language, error))
             }
         };
 
-        let weights: PunktParameters = serde_json::from_str(&json_file_string).expect("Unable to parse weight file.");
+        let profile = match language {
+            Some(language) => LanguageProfile::for_language(language),
+            None => LanguageProfile::default()
+        };
+
+        Self::from_weights(weights, &profile)
+    }
 
+    /// Loads the weights from `resource`, which may be a file, a download
+    /// or bytes in memory.
+    pub fn from_resource(resource: &Resource, language: Option<Language>) -> std::result::Result<Self, SentenceSplitterError> {
+        let profile = match language {
+            Some(language) => LanguageProfile::for_language(language),
+            None => LanguageProfile::default()
+        };
+        Ok(Self::from_weights(Self::_load_weights(resource)?, &profile))
+    }
+
+    pub(crate) fn from_weights(weights: PunktParameters, profile: &LanguageProfile) -> Self {
         Self {
-            punkt_base_class: PunktBaseClass::new(None, Some(weights)),
+            punkt_base_class: PunktBaseClass::new(Some(PunktLanguageVars::from_profile(profile)), Some(weights)),
+            realign_boundaries: true,
         }
     }
 
+    /// The registered weights of a custom language, otherwise the download
+    /// of a built-in one.
+    pub(crate) fn _language_weights(language: Language) -> std::result::Result<Resource, SentenceSplitterError> {
+        if let Language::Custom(id) = language {
+            return get_custom_language(&language)
+                .map(|custom_language| custom_language.weights)
+                .ok_or_else(|| SentenceSplitterError::LanguageError(format!("{} is not registered", id.name())));
+        }
+        Self::_builtin_weights(language)
+    }
+
+    #[cfg(feature = "remote")]
+    fn _builtin_weights(language: Language) -> std::result::Result<Resource, SentenceSplitterError> {
+        let resource = RemoteResource::new(
+            weights_url(language).as_str(),
+            format!("punkt/{}", language).as_str(),
+        );
+        Ok(Resource::Remote(match weights_sha256(language) {
+            Some(sha256) => resource.with_sha256(sha256),
+            None => resource
+        }))
+    }
+
+    #[cfg(not(feature = "remote"))]
+    fn _builtin_weights(_language: Language) -> std::result::Result<Resource, SentenceSplitterError> {
+        Err(SentenceSplitterError::FeatureError(String::from("remote")))
+    }
+
+    pub(crate) fn _load_weights(resource: &Resource) -> std::result::Result<PunktParameters, SentenceSplitterError> {
+        Ok(serde_json::from_slice(&resource.read()?)?)
+    }
+
+    /// Replaces the language-specific settings, keeping the loaded weights.
+    pub fn with_profile(mut self, profile: LanguageProfile) -> Self {
+        self.punkt_base_class.lang_vars = PunktLanguageVars::from_profile(&profile);
+        self
+    }
+
+    /// Whether `sentences` moves closing punctuation back to the end of the
+    /// previous sentence. On by default.
+    pub fn with_realign_boundaries(mut self, realign_boundaries: bool) -> Self {
+        self.realign_boundaries = realign_boundaries;
+        self
+    }
+
     pub fn tokenize(&self, text: &str, realign_boundaries: bool) -> Vec<String> {
         self.sentences_from_text(text, realign_boundaries)
     }
 
-    fn span_tokenize(&self, text: &str, realign_boundaries: bool) -> Vec<(usize, usize)> {
+    /// Like `tokenize`, with the tokenizer's realignment setting.
+    pub fn sentences(&self, text: &str) -> Vec<String> {
+        self.sentences_from_text(text, self.realign_boundaries)
+    }
+
+    pub(crate) fn span_tokenize(&self, text: &str, realign_boundaries: bool) -> Vec<(usize, usize)> {
         let mut slices = self._slices_from_text(text);
 
         if realign_boundaries {
@@ -440,8 +592,19 @@ impl PunktSentenceTokenizer<'_> {
         let mut last_break = 0;
         let mut slice: Vec<Slice> = Vec::new();
 
+        // (end of sentence, start of the next one)
+        let mut breaks: Vec<(usize, usize)> = Vec::new();
+
+        let cjk_boundary_re = &self.punkt_base_class.lang_vars.punkt_language_pros.cjk_boundary_re;
+
         for _match in self.punkt_base_class.lang_vars.punkt_language_pros.period_context_re.captures_iter(text) {
 
+            // full-width terminators are left to `cjk_boundary_re`
+            if cjk_boundary_re.is_some()
+                && _match.as_ref().unwrap().get(0).unwrap().as_str().ends_with(|c| CJK_SENT_END_CHARS.contains(c)) {
+                continue
+            }
+
             let context = format!(
                 "{group}{after_tok}",
                 group=_match.as_ref().unwrap().get(0).expect("No").as_str(),
@@ -449,28 +612,117 @@ impl PunktSentenceTokenizer<'_> {
             );
 
             if self.text_contains_sentbreak(&context) {
-                slice.push(
-                    Slice {
-                        start: last_break,
-                        end: _match.as_ref().unwrap().get(0).unwrap().end()
+                breaks.push((
+                    _match.as_ref().unwrap().get(0).unwrap().end(),
+                    match _match.as_ref().unwrap().name("next_tok") {
+                        Some(m) => m.start(),
+                        None => _match.as_ref().unwrap().get(0).unwrap().end()
                     }
-                );
+                ));
+            }
+        }
 
-                last_break = match _match.as_ref().unwrap().name("next_tok") {
-                    Some(m) => m.start(),
-                    None => _match.as_ref().unwrap().get(0).unwrap().end()
-                }
+        if let Some(cjk_boundary_re) = cjk_boundary_re {
+            for m in cjk_boundary_re.find_iter(text).flatten() {
+                let skipped = text[m.end()..].len() - text[m.end()..].trim_start().len();
+                breaks.push((m.end(), m.end() + skipped));
+            }
+            breaks.sort_unstable();
+            breaks.dedup_by_key(|b| b.0);
+        }
+
+        if let Some(quote_pairs) = &self.punkt_base_class.lang_vars.punkt_language_static_vars.quote_pairs {
+            let quotations = self._quotations_from_text(text, quote_pairs);
+            breaks.retain(|(end, _)| !self._is_inside_dialogue(text, *end, &quotations));
+        }
+
+        if self.punkt_base_class.lang_vars.punkt_language_static_vars.ordinal_numbers {
+            let markers = self._enumeration_markers(text);
+            breaks.retain(|(end, _)| !markers.contains(end));
+        }
+
+        let paired_marks = &self.punkt_base_class.lang_vars.punkt_language_static_vars.paired_marks;
+        if !paired_marks.is_empty() {
+            let pairs = self._quotations_from_text(text, paired_marks);
+            breaks.retain(|(end, _)| !self._is_inside_paired_marks(text, *end, &pairs));
+        }
+
+        for (end, next_start) in breaks {
+            if end < last_break {
+                continue
             }
+            slice.push(
+                Slice {
+                    start: last_break,
+                    end
+                }
+            );
+            last_break = next_start;
         }
         slice.push(
             Slice {
                 start: last_break,
-                end: text.trim_start().len()
+                end: text.trim_end().len()
             }
         );
         slice
     }
 
+    /// Byte spans of the balanced quotations in `text`, from the opening mark
+    /// to the end of the closing one.
+    fn _quotations_from_text(&self, text: &str, quote_pairs: &[(char, char)]) -> Vec<Slice> {
+        let mut quotations: Vec<Slice> = Vec::new();
+        let mut open: Vec<(char, usize)> = Vec::new();
+        let mut prev: Option<char> = None;
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((pos, c)) = chars.next() {
+            let next = chars.peek().map(|x| x.1);
+            let last = prev;
+            prev = Some(c);
+
+            // apostrophes such as in don’t
+            if last.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric) {
+                continue
+            }
+
+            let closes = open.iter().rposition(|(opening, _)| quote_pairs.contains(&(*opening, c)));
+            if let Some(index) = closes {
+                quotations.push(Slice { start: open[index].1, end: pos + c.len_utf8() });
+                open.truncate(index);
+            } else if quote_pairs.iter().any(|(opening, _)| *opening == c) {
+                open.push((c, pos));
+            }
+        }
+        quotations
+    }
+
+    fn _is_inside_dialogue(&self, text: &str, position: usize, quotations: &[Slice]) -> bool {
+        let innermost = quotations.iter()
+            .filter(|q| q.start < position && position < q.end)
+            .min_by_key(|q| q.end - q.start);
+
+        match innermost {
+            Some(quotation) => match text[quotation.end..].trim_start().chars().next() {
+                Some(c) => c.is_lowercase() || self.punkt_base_class.lang_vars.punkt_language_static_vars.internal_punctuation.contains(c),
+                None => false
+            },
+            None => false
+        }
+    }
+
+    /// A boundary may not fall inside an open `¿…?` or `¡…!` pair, nor
+    /// directly after one that the sentence continues from in lowercase.
+    fn _is_inside_paired_marks(&self, text: &str, position: usize, pairs: &[Slice]) -> bool {
+        pairs.iter().any(|pair| {
+            pair.start < position && (
+                position < pair.end
+                ||
+                (position == pair.end && text[position..].trim_start().starts_with(char::is_lowercase))
+            )
+        })
+    }
+
     fn _realign_boundaries(&self, text: &str, slices: Vec<Slice>) -> Vec<Slice> {
         let mut boundaries: Vec<Slice> = Vec::new();
         let mut realign: usize = 0;
@@ -496,7 +748,7 @@ impl PunktSentenceTokenizer<'_> {
             if m.is_some() {
                 boundaries.push(Slice {
                     start: sl1.start,
-                    end: sl2.unwrap().start + m.unwrap().as_str().trim_start().len()
+                    end: sl2.unwrap().start + m.unwrap().as_str().trim_end().len()
                 });
                 realign = m.unwrap().end()
             } else {
@@ -551,6 +803,13 @@ impl PunktSentenceTokenizer<'_> {
         let next_typ = aug_tok2.as_ref().unwrap().type_no_sent_period();
         let tok_is_initial = aug_tok1.is_initial();
 
+        // an opening `¿` or `¡` always starts a new sentence
+        if self.punkt_base_class.lang_vars.punkt_language_static_vars.paired_marks.iter()
+            .any(|(opening, _)| aug_tok2.as_ref().unwrap().token.starts_with(*opening)) {
+            aug_tok1.sent_break = Some(true);
+            return
+        }
+
         if self.punkt_base_class.params.collocations.contains(
             &Collocations(typ.clone(), next_typ.clone())
         ) {
@@ -559,6 +818,17 @@ impl PunktSentenceTokenizer<'_> {
             return;
         }
 
+        if self.punkt_base_class.lang_vars.punkt_language_static_vars.ordinal_numbers
+            &&
+            (typ == "##number##" || aug_tok1.is_roman_numeral())
+            &&
+            self._is_ordinal_follower(aug_tok2.as_ref().unwrap(), &next_typ)
+        {
+            aug_tok1.sent_break = Some(false);
+            aug_tok1.abbr = Some(true);
+            return
+        }
+
         if (aug_tok1.abbr.unwrap_or(false) || aug_tok1.ellipsis.unwrap_or(false)) && !tok_is_initial {
             let is_sent_starter: Return = self._ortho_heuristic(&aug_tok2.as_ref().unwrap());
 
@@ -567,7 +837,8 @@ impl PunktSentenceTokenizer<'_> {
                 return
             }
 
-            if aug_tok2.as_ref().unwrap().first_upper() && self.punkt_base_class.params.sent_starters.contains(next_typ.as_str()) {
+            if (aug_tok2.as_ref().unwrap().first_upper() || self._is_uncased(aug_tok2.as_ref().unwrap()))
+                && self.punkt_base_class.params.sent_starters.contains(next_typ.as_str()) {
                 aug_tok1.sent_break = Some(true);
                 return;
             }
@@ -597,11 +868,102 @@ impl PunktSentenceTokenizer<'_> {
         }
     }
 
+    /// Case-free variant of `_ortho_heuristic`: a learned sentence starter
+    /// starts a sentence, and a word learned to follow abbreviations in
+    /// collocations does not.
+    fn _statistics_heuristic(&self, aug_tok: &PunktToken) -> Return<'_> {
+        let typ = aug_tok.type_no_sent_period();
+
+        if self.punkt_base_class.params.sent_starters.contains(&typ) {
+            return Return::Bool(true)
+        }
+
+        if self.punkt_base_class.collocation_followers.contains(&typ) {
+            return Return::Bool(false)
+        }
+
+        Return::String("unknown")
+    }
+
+    /// Whether the token after a number such as `3.` or `II.` shows the number
+    /// is an ordinal (`3. Oktober`, `der 2. Weltkrieg`, `Friedrich II. war`).
+    fn _is_ordinal_follower(&self, aug_tok: &PunktToken, typ: &str) -> bool {
+        if self.punkt_base_class.lang_vars.punkt_language_static_vars.month_names.contains(typ.trim_end_matches('.')) {
+            return true
+        }
+
+        let ortho_context = self.punkt_base_class.params.get_ortho_context(typ);
+        let is_sent_starter = self.punkt_base_class.params.sent_starters.contains(typ);
+
+        // a word known in lowercase that does not start sentences; unknown
+        // words are left to the orthographic heuristic
+        if aug_tok.first_lower() {
+            return (ortho_context & _ORTHO_LC) != 0 && !is_sent_starter
+        }
+
+        // a noun: only ever seen capitalised in the middle of a sentence
+
+        aug_tok.first_upper()
+            &&
+            (ortho_context & _ORTHO_MID_UC) != 0
+            &&
+            (ortho_context & _ORTHO_LC) == 0
+            &&
+            !is_sent_starter
+    }
+
+    /// End positions of the numbers in `1. … 2. … 3. …` lists. A list starts
+    /// with `1.` at the beginning of a line or after a colon, and goes on
+    /// with the following numbers in order.
+    fn _enumeration_markers(&self, text: &str) -> HashSet<usize> {
+        let mut markers: HashSet<usize> = HashSet::new();
+        let mut chain: Vec<usize> = Vec::new();
+        let mut expected: usize = 1;
+
+        for captures in _RE_ENUMERATION_MARKER.captures_iter(text).flatten() {
+            let value: usize = captures.get(1).unwrap().as_str().parse().unwrap();
+            let start = captures.get(0).unwrap().start();
+            let end = captures.get(0).unwrap().end();
+
+            if value != expected {
+                if chain.len() > 1 {
+                    markers.extend(&chain);
+                }
+                chain.clear();
+                expected = 1;
+                if value != expected {
+                    continue
+                }
+            }
+            let before = text[..start].trim_end_matches([' ', '\t']);
+            if value == 1 && !(before.is_empty() || before.ends_with(['\n', ':'])) {
+                continue
+            }
+            chain.push(end);
+            expected += 1;
+        }
+        if chain.len() > 1 {
+            markers.extend(chain);
+        }
+        markers
+    }
+
+    fn _is_uncased(&self, aug_tok: &PunktToken) -> bool {
+        self.punkt_base_class.lang_vars.punkt_language_static_vars.ortho_heuristic == OrthoHeuristic::Statistics
+            && !aug_tok.first_cased()
+    }
+
     fn _ortho_heuristic(&self, aug_tok: &PunktToken) -> Return {
-        if [";", ":", ",", ".", "!", "?"].contains(&aug_tok.token.as_str()) {
+        if [";", ":", ",", ".", "!", "?"].contains(&aug_tok.token.as_str())
+            ||
+            self.punkt_base_class.lang_vars.punkt_language_static_vars.sent_end_chars.contains(&aug_tok.token) {
             return Return::Bool(false)
         }
 
+        if self._is_uncased(aug_tok) {
+            return self._statistics_heuristic(aug_tok)
+        }
+
         let ortho_context = self.punkt_base_class.params.get_ortho_context(&aug_tok.type_no_sent_period());
 
         if aug_tok.first_upper() && (ortho_context & _ORTHO_LC) != 0 && !(ortho_context & _ORTHO_MID_UC) != 0 {
@@ -733,4 +1095,684 @@ mod punkt_parameters_tests {
         assert_eq!(from_struct.get_ortho_context("a"), 254);
         assert_eq!(from_struct.ortho_context.len(), 4);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod punkt_resource_tests {
+    use super::*;
+    use crate::helper::resources::LocalResource;
+    use std::path::PathBuf;
+
+    static ENGLISH_WEIGHTS: &[u8] = include_bytes!("../../data/weights/English.json");
+    static TEXT: &str = "Mr. Smith arrived at 5 p.m. on Monday. He left early.";
+
+    #[test]
+    fn test_tokenizer_from_memory() {
+        let expected = PunktSentenceTokenizer::new(Some("data/weights/English.json"), Some(Language::English)).tokenize(TEXT, true);
+
+        let resources = vec![
+            Resource::Static(ENGLISH_WEIGHTS),
+            Resource::Bytes(ENGLISH_WEIGHTS.to_vec()),
+            Resource::Local(LocalResource { local_path: PathBuf::from("data/weights/English.json") }),
+        ];
+        for resource in resources {
+            let tokenizer = PunktSentenceTokenizer::from_resource(&resource, Some(Language::English)).unwrap();
+            assert_eq!(tokenizer.tokenize(TEXT, true), expected);
+        }
+    }
+
+    #[test]
+    fn test_invalid_weights() {
+        assert!(matches!(
+            PunktSentenceTokenizer::from_resource(&Resource::Static(b"not json"), None),
+            Err(SentenceSplitterError::WeightFileError(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod punkt_whitespace_tests {
+    use super::*;
+
+    fn tokenizer() -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(Some("data/weights/English.json"), Some(Language::English))
+    }
+
+    #[test]
+    fn test_leading_whitespace_keeps_the_last_sentence() {
+        // the last slice used to end at the length of the text without its
+        // leading whitespace, cutting the last sentence short
+        assert_eq!(tokenizer().tokenize("  One. Two.", true), vec!["  One.", "Two."]);
+        assert_eq!(tokenizer().tokenize("  One. Two.", false), vec!["  One.", "Two."]);
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        assert_eq!(tokenizer().tokenize("One. Two.  \n", true), vec!["One.", "Two."]);
+    }
+}
+
+#[cfg(test)]
+mod punkt_cjk_tests {
+
+    use crate::tokenize::profile::LanguageProfile;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn tokenizer(cjk: bool) -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/English.json"),
+            None
+        ).with_profile(LanguageProfile::new().cjk(cjk))
+    }
+
+    #[test]
+    fn test_chinese_without_whitespace() {
+        let sentences = tokenizer(true).tokenize("我今天去了学校。然后我学习了中文！你呢？", true);
+
+        assert_eq!(sentences, vec!["我今天去了学校。", "然后我学习了中文！", "你呢？"]);
+    }
+
+    #[test]
+    fn test_japanese_closing_brackets() {
+        let sentences = tokenizer(true).tokenize("「本当ですか？」彼は聞いた。『はい』と答えた。", true);
+
+        assert_eq!(sentences, vec!["「本当ですか？」", "彼は聞いた。", "『はい』と答えた。"]);
+    }
+
+    #[test]
+    fn test_mixed_cjk_latin() {
+        let sentences = tokenizer(true).tokenize("Mr. Smith went to Beijing. 他很喜欢北京。The weather was great!", true);
+
+        assert_eq!(sentences, vec!["Mr. Smith went to Beijing.", "他很喜欢北京。", "The weather was great!"]);
+    }
+
+    #[test]
+    fn test_cjk_disabled_by_default() {
+        let sentences = tokenizer(false).tokenize("我今天去了学校。然后我学习了中文。", true);
+
+        assert_eq!(sentences.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod punkt_realignment_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::LanguageProfile;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn tokenizer(language: Language) -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some(&format!("data/weights/{}.json", language)),
+            Some(language)
+        )
+    }
+
+    #[test]
+    fn test_english_quotes() {
+        let sentences = tokenizer(Language::English).tokenize("He said “Stop.” Then he left. ‘Fine.’ She agreed.", true);
+
+        assert_eq!(sentences, vec!["He said “Stop.”", "Then he left.", "‘Fine.’", "She agreed."]);
+    }
+
+    #[test]
+    fn test_english_opening_quote_not_moved() {
+        let sentences = tokenizer(Language::English).tokenize("He left. “Hi,” she said.", true);
+
+        assert_eq!(sentences, vec!["He left.", "“Hi,” she said."]);
+    }
+
+    #[test]
+    fn test_ascii_quotes_without_trailing_whitespace() {
+        let sentences = tokenizer(Language::English).tokenize(r#"He said "Stop." Then he left."#, true);
+
+        assert_eq!(sentences, vec![r#"He said "Stop.""#, "Then he left."]);
+    }
+
+    #[test]
+    fn test_french_guillemets() {
+        let sentences = tokenizer(Language::French).tokenize("Il a dit « Non. » Puis il est parti.", true);
+
+        assert_eq!(sentences, vec!["Il a dit « Non. »", "Puis il est parti."]);
+    }
+
+    #[test]
+    fn test_german_quotes() {
+        let tokenizer = tokenizer(Language::German);
+
+        assert_eq!(
+            tokenizer.tokenize("Er sagte „Halt.“ Dann ging er.", true),
+            vec!["Er sagte „Halt.“", "Dann ging er."]
+        );
+        assert_eq!(
+            tokenizer.tokenize("Er sagte »Halt.« Dann ging er.", true),
+            vec!["Er sagte »Halt.«", "Dann ging er."]
+        );
+    }
+
+    #[test]
+    fn test_japanese_brackets() {
+        let tokenizer = tokenizer(Language::English).with_profile(LanguageProfile::new().cjk(true));
+
+        assert_eq!(
+            tokenizer.tokenize("「行きます。」 『待って！』 彼は走った。", true),
+            vec!["「行きます。」", "『待って！』", "彼は走った。"]
+        );
+    }
+
+    #[test]
+    fn test_without_closing_punctuation() {
+        // nothing is moved back, but the profile is valid
+        let tokenizer = tokenizer(Language::English).with_profile(
+            LanguageProfile::new().unicode_closing_punctuation(false).closing_punctuation("")
+        );
+
+        assert_eq!(
+            tokenizer.tokenize(r#"He said "Stop." Then he left. Fine."#, true),
+            vec![r#"He said "Stop."#, r#"" Then he left."#, "Fine."]
+        );
+    }
+
+    #[test]
+    fn test_configurable_closing_punctuation() {
+        let tokenizer = tokenizer(Language::English).with_profile(
+            LanguageProfile::new().unicode_closing_punctuation(false).add_closing_punctuation("»")
+        );
+
+        assert_eq!(
+            tokenizer.tokenize("Il a dit « Non. » Puis il est parti.", true),
+            vec!["Il a dit « Non. »", "Puis il est parti."]
+        );
+        assert_eq!(
+            tokenizer.tokenize("He said “Stop.” Then he left.", true),
+            vec!["He said “Stop.” Then he left."]
+        );
+    }
+}
+
+#[cfg(test)]
+mod punkt_dialogue_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::LanguageProfile;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn tokenizer(language: Language, profile: LanguageProfile) -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some(&format!("data/weights/{}.json", language)),
+            Some(language)
+        ).with_profile(profile)
+    }
+
+    fn english() -> PunktSentenceTokenizer {
+        tokenizer(Language::English, LanguageProfile::new().quote_aware(true))
+    }
+
+    #[test]
+    fn test_dialogue_tag() {
+        assert_eq!(
+            english().tokenize(r#""Stop!" she said. Then he left."#, true),
+            vec![r#""Stop!" she said."#, "Then he left."]
+        );
+        assert_eq!(
+            english().tokenize("“Stop! Wait!” she shouted. “Why?” He did not answer.", true),
+            vec!["“Stop! Wait!” she shouted.", "“Why?”", "He did not answer."]
+        );
+    }
+
+    #[test]
+    fn test_nested_quotes() {
+        assert_eq!(
+            english().tokenize("“He told me, ‘Run!’ and I ran.” She nodded.", true),
+            vec!["“He told me, ‘Run!’ and I ran.”", "She nodded."]
+        );
+    }
+
+    #[test]
+    fn test_apostrophe_is_not_a_closing_quote() {
+        assert_eq!(
+            english().tokenize("‘I don’t know!’ she said.", true),
+            vec!["‘I don’t know!’ she said."]
+        );
+    }
+
+    #[test]
+    fn test_quote_aware_disabled_by_default() {
+        let tokenizer = tokenizer(Language::English, LanguageProfile::new());
+
+        assert_eq!(
+            tokenizer.tokenize(r#""Stop!" she said."#, true),
+            vec![r#""Stop!""#, "she said."]
+        );
+    }
+
+    #[test]
+    fn test_configurable_quote_pairs() {
+        let german = tokenizer(Language::German, LanguageProfile::for_language(Language::German).quote_aware(true));
+
+        assert_eq!(
+            german.tokenize("„Halt!“ rief er. Dann ging er.", true),
+            vec!["„Halt!“ rief er.", "Dann ging er."]
+        );
+
+        let curly_only = tokenizer(Language::English, LanguageProfile::new().quote_aware(true).quote_pairs(&[('“', '”')]));
+
+        assert_eq!(
+            curly_only.tokenize(r#""Stop!" she said."#, true),
+            vec![r#""Stop!""#, "she said."]
+        );
+    }
+}
+
+#[cfg(test)]
+mod punkt_spanish_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn spanish() -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/Spanish.json"),
+            Some(Language::Spanish)
+        )
+    }
+
+    #[test]
+    fn test_inverted_marks() {
+        assert_eq!(
+            spanish().tokenize("¿Cómo estás? ¡Muy bien! Gracias por preguntar.", true),
+            vec!["¿Cómo estás?", "¡Muy bien!", "Gracias por preguntar."]
+        );
+    }
+
+    #[test]
+    fn test_opening_mark_starts_sentence() {
+        assert_eq!(
+            spanish().tokenize("Hola.¿Qué tal? Bien.", true),
+            vec!["Hola.", "¿Qué tal?", "Bien."]
+        );
+        assert_eq!(
+            spanish().tokenize("Compré pan, leche, etc. ¿Algo más? No.", true),
+            vec!["Compré pan, leche, etc.", "¿Algo más?", "No."]
+        );
+    }
+
+    #[test]
+    fn test_embedded_question() {
+        assert_eq!(
+            spanish().tokenize("Le pregunté ¿vienes mañana? y no contestó. Luego se fue.", true),
+            vec!["Le pregunté ¿vienes mañana? y no contestó.", "Luego se fue."]
+        );
+    }
+
+    #[test]
+    fn test_no_boundary_inside_open_pair() {
+        assert_eq!(
+            spanish().tokenize("¡Mira el cartel. Dice algo! Vale.", true),
+            vec!["¡Mira el cartel. Dice algo!", "Vale."]
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_mark() {
+        assert_eq!(
+            spanish().tokenize("¿Qué pasó. Nadie lo sabe.", true),
+            vec!["¿Qué pasó.", "Nadie lo sabe."]
+        );
+    }
+}
+
+#[cfg(test)]
+mod punkt_french_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::LanguageProfile;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn french() -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/French.json"),
+            Some(Language::French)
+        )
+    }
+
+    #[test]
+    fn test_space_before_terminator() {
+        for space in &[" ", "\u{a0}", "\u{202f}"] {
+            let text = format!("Vous venez{s}? Oui{s}! Très bien.", s=space);
+
+            assert_eq!(
+                french().tokenize(&text, true),
+                vec![format!("Vous venez{}?", space), format!("Oui{}!", space), String::from("Très bien.")]
+            );
+        }
+    }
+
+    #[test]
+    fn test_space_before_colon_and_semicolon() {
+        assert_eq!(
+            french().tokenize("Mme Durand est là\u{202f}; M. Dupont aussi. Il a dit\u{a0}: partons.", true),
+            vec!["Mme Durand est là\u{202f}; M. Dupont aussi.", "Il a dit\u{a0}: partons."]
+        );
+    }
+
+    #[test]
+    fn test_abbreviation_before_spaced_terminator() {
+        assert_eq!(
+            french().tokenize("Avez-vous vu M. Dupont\u{202f}? Non, pas encore.", true),
+            vec!["Avez-vous vu M. Dupont\u{202f}?", "Non, pas encore."]
+        );
+    }
+
+    #[test]
+    fn test_guillemets() {
+        assert_eq!(
+            french().tokenize("«\u{a0}Bonjour\u{202f}!\u{a0}» Il sourit. Il a crié «Attention\u{202f}!» Puis il a couru.", true),
+            vec!["«\u{a0}Bonjour\u{202f}!\u{a0}»", "Il sourit.", "Il a crié «Attention\u{202f}!»", "Puis il a couru."]
+        );
+        assert_eq!(
+            french().tokenize("Il part.«\u{a0}Adieu\u{a0}», dit-il.", true),
+            vec!["Il part.", "«\u{a0}Adieu\u{a0}», dit-il."]
+        );
+    }
+
+    #[test]
+    fn test_dialogue() {
+        let tokenizer = french().with_profile(LanguageProfile::for_language(Language::French).quote_aware(true));
+
+        assert_eq!(
+            tokenizer.tokenize("«\u{a0}Ça va\u{a0}?\u{a0}» répondit-elle. Oui.", true),
+            vec!["«\u{a0}Ça va\u{a0}?\u{a0}» répondit-elle.", "Oui."]
+        );
+    }
+
+    #[test]
+    fn test_surrounding_no_break_spaces() {
+        assert_eq!(
+            french().tokenize("\u{202f}Bonjour. Ça va\u{202f}?\u{a0}", true),
+            vec!["\u{202f}Bonjour.", "Ça va\u{202f}?"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod punkt_case_folding_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::{CaseFolding, LanguageProfile};
+    use crate::tokenize::punkt::{PunktSentenceTokenizer, PunktToken};
+
+    fn turkish(profile: LanguageProfile) -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/Turkish.json"),
+            Some(Language::Turkish)
+        ).with_profile(profile)
+    }
+
+    #[test]
+    fn test_turkic_lowercase() {
+        assert_eq!(CaseFolding::Turkic.to_lowercase("İSTANBUL IRAK"), "istanbul ırak");
+        assert_eq!(CaseFolding::Default.to_lowercase("İSTANBUL IRAK"), "i\u{307}stanbul irak");
+    }
+
+    #[test]
+    fn test_turkish_abbreviations() {
+        let annotated = |tokenizer: PunktSentenceTokenizer| {
+            let base = &tokenizer.punkt_base_class;
+            base._annotate_first_pass(base._tokenize_words("R.İ. İDİ."))
+                .into_iter()
+                .map(|t| t.abbr.unwrap_or(false))
+                .collect::<Vec<bool>>()
+        };
+
+        assert_eq!(annotated(turkish(LanguageProfile::for_language(Language::Turkish))), vec![true, true]);
+        assert_eq!(annotated(turkish(LanguageProfile::new())), vec![false, false]);
+    }
+
+    #[test]
+    fn test_turkish_sent_starter() {
+        let text = "Kalem, defter vs. İstanbul her zaman pahalıdır.";
+
+        assert_eq!(
+            turkish(LanguageProfile::for_language(Language::Turkish)).tokenize(text, true),
+            vec!["Kalem, defter vs.", "İstanbul her zaman pahalıdır."]
+        );
+        assert_eq!(
+            turkish(LanguageProfile::new()).tokenize(text, true),
+            vec![text]
+        );
+    }
+
+    #[test]
+    fn test_titlecase_digraph() {
+        let token = PunktToken::new("ǅemal", false, false, CaseFolding::Default);
+
+        assert!(token.first_upper());
+        assert!(!token.first_lower());
+        assert_eq!(token.type_, "ǆemal");
+    }
+}
+
+#[cfg(test)]
+mod punkt_german_ordinal_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::LanguageProfile;
+    use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+    fn german() -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/German.json"),
+            Some(Language::German)
+        )
+    }
+
+    static DATES: &[(&str, &[&str])] = &[
+        ("Am 3. Oktober feiern wir. Das ist schön.", &["Am 3. Oktober feiern wir.", "Das ist schön."]),
+        ("Die Feier ist am 1. Mai. Kommst du?", &["Die Feier ist am 1. Mai.", "Kommst du?"]),
+        ("Er wurde am 24. Dezember 1990 geboren. Sie auch.", &["Er wurde am 24. Dezember 1990 geboren.", "Sie auch."]),
+        ("Am 3. Okt. 1990 kam die Einheit. Alle feierten.", &["Am 3. Okt. 1990 kam die Einheit.", "Alle feierten."]),
+        ("Seit dem 1. Jan. gilt das Gesetz. Es ist neu.", &["Seit dem 1. Jan. gilt das Gesetz.", "Es ist neu."]),
+    ];
+
+    static ORDINALS: &[(&str, &[&str])] = &[
+        ("Der 2. Weltkrieg endete 1945. Danach kam der Frieden.", &["Der 2. Weltkrieg endete 1945.", "Danach kam der Frieden."]),
+        ("Das war im 19. Jahrhundert. Heute ist es anders.", &["Das war im 19. Jahrhundert.", "Heute ist es anders."]),
+        ("Er kam als 3. ins Ziel. Sie war 5. Das war knapp.", &["Er kam als 3. ins Ziel.", "Sie war 5.", "Das war knapp."]),
+        ("Friedrich II. war König. Er regierte lange.", &["Friedrich II. war König.", "Er regierte lange."]),
+        ("Er wurde 1990 geboren. Er lebt in Berlin.", &["Er wurde 1990 geboren.", "Er lebt in Berlin."]),
+    ];
+
+    static ENUMERATIONS: &[(&str, &[&str])] = &[
+        ("Punkte: 1. Einkaufen 2. Kochen 3. Essen. Fertig.", &["Punkte: 1. Einkaufen 2. Kochen 3. Essen.", "Fertig."]),
+        ("Schritte: 1. Öffnen 2. Lesen. Dann schließen.", &["Schritte: 1. Öffnen 2. Lesen.", "Dann schließen."]),
+        ("Schritte:\n1. Öffnen\n2. Lesen. Dann schließen.", &["Schritte:\n1. Öffnen\n2. Lesen.", "Dann schließen."]),
+    ];
+
+    static SENTENCE_ENDS: &[(&str, &[&str])] = &[
+        ("Der Verlust belief sich auf 1. Sie rechnete nach. Es waren 2. Danach gingen alle.", &["Der Verlust belief sich auf 1.", "Sie rechnete nach.", "Es waren 2.", "Danach gingen alle."]),
+        ("Das steht in Kapitel 12. Danach folgt der Anhang.", &["Das steht in Kapitel 12.", "Danach folgt der Anhang."]),
+    ];
+
+    fn assert_corpus(tokenizer: &PunktSentenceTokenizer, corpus: &[(&str, &[&str])]) {
+        for (text, expected) in corpus {
+            assert_eq!(&tokenizer.tokenize(text, true), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_corpus(&german(), DATES);
+    }
+
+    #[test]
+    fn test_ordinals() {
+        assert_corpus(&german(), ORDINALS);
+    }
+
+    #[test]
+    fn test_enumerations() {
+        assert_corpus(&german(), ENUMERATIONS);
+    }
+
+    #[test]
+    fn test_numbers_ending_sentences() {
+        assert_corpus(&german(), SENTENCE_ENDS);
+    }
+
+    #[test]
+    fn test_ordinal_numbers_follow_profile() {
+        let tokenizer = german().with_profile(LanguageProfile::for_language(Language::German).ordinal_numbers(false));
+
+        assert_eq!(
+            tokenizer.tokenize("Der 2. Weltkrieg endete 1945.", true),
+            vec!["Der 2.", "Weltkrieg endete 1945."]
+        );
+    }
+}
+
+#[cfg(test)]
+mod punkt_uncased_tests {
+
+    use crate::tokenize::profile::{LanguageProfile, OrthoHeuristic};
+    use crate::tokenize::punkt::{PunktParameters, PunktSentenceTokenizer};
+
+    static WEIGHT: &str = r#"
+        {
+            "abbrev_types": ["आदि", "डॉ"],
+            "collocations": [["डॉ", "अध्याय"]],
+            "sent_starters": ["वह"],
+            "ortho_context": {"then": 32}
+        }
+    "#;
+
+    fn tokenizer(ortho_heuristic: OrthoHeuristic) -> PunktSentenceTokenizer {
+        let params: PunktParameters = serde_json::from_str(WEIGHT).unwrap();
+
+        PunktSentenceTokenizer::from_weights(params, &LanguageProfile::new().ortho_heuristic(ortho_heuristic))
+    }
+
+    #[test]
+    fn test_abbreviation_before_sent_starter() {
+        let text = "उन्होंने फल, सब्ज़ी आदि. वह चले गए.";
+
+        assert_eq!(
+            tokenizer(OrthoHeuristic::Statistics).tokenize(text, true),
+            vec!["उन्होंने फल, सब्ज़ी आदि.", "वह चले गए."]
+        );
+        assert_eq!(
+            tokenizer(OrthoHeuristic::Case).tokenize(text, true),
+            vec![text]
+        );
+    }
+
+    #[test]
+    fn test_number_before_collocation_follower() {
+        let text = "खंड 5. अध्याय 2 देखें.";
+
+        assert_eq!(
+            tokenizer(OrthoHeuristic::Statistics).tokenize(text, true),
+            vec![text]
+        );
+        assert_eq!(
+            tokenizer(OrthoHeuristic::Case).tokenize(text, true),
+            vec!["खंड 5.", "अध्याय 2 देखें."]
+        );
+    }
+
+    #[test]
+    fn test_cased_words_keep_case_heuristic() {
+        // `then` was seen in lowercase, so the capitalised word starts a
+        // sentence, while the statistics know nothing about it
+        let text = "उन्होंने फल, सब्ज़ी आदि. Then they left.";
+
+        for ortho_heuristic in [OrthoHeuristic::Statistics, OrthoHeuristic::Case] {
+            assert_eq!(
+                tokenizer(ortho_heuristic).tokenize(text, true),
+                vec!["उन्होंने फल, सब्ज़ी आदि.", "Then they left."]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod punkt_ellipsis_tests {
+
+    use crate::helper::common::Language;
+    use crate::tokenize::profile::CaseFolding;
+    use crate::tokenize::punkt::{PunktSentenceTokenizer, PunktToken};
+
+    fn english() -> PunktSentenceTokenizer {
+        PunktSentenceTokenizer::new(
+            Some("data/weights/English.json"),
+            Some(Language::English)
+        )
+    }
+
+    #[test]
+    fn test_is_ellipsis() {
+        for token in &["...", "..", "…", "……", ".…", "…."] {
+            let token = PunktToken::new(token, false, false, CaseFolding::Default);
+
+            assert!(token.is_ellipsis());
+            assert!(token.period_final);
+        }
+        assert!(!PunktToken::new(".", false, false, CaseFolding::Default).is_ellipsis());
+    }
+
+    #[test]
+    fn test_unicode_ellipsis() {
+        assert_eq!(english().tokenize("Wait… Then he left.", true), vec!["Wait…", "Then he left."]);
+        assert_eq!(english().tokenize("Hmm…… Fine.", true), vec!["Hmm……", "Fine."]);
+        assert_eq!(english().tokenize("He paused… and then went on.", true), vec!["He paused… and then went on."]);
+    }
+
+    #[test]
+    fn test_word_ending_in_unicode_ellipsis() {
+        let tokenizer = english();
+
+        let mut abbreviation = PunktToken::new("Ltd…", false, false, CaseFolding::Default);
+        tokenizer.punkt_base_class._first_pass_annotation(&mut abbreviation);
+        assert_eq!(abbreviation.abbr, Some(true));
+
+        let mut word = PunktToken::new("Hmm…", false, false, CaseFolding::Default);
+        tokenizer.punkt_base_class._first_pass_annotation(&mut word);
+        assert_eq!(word.sent_break, Some(true));
+    }
+
+    #[test]
+    fn test_ascii_ellipsis() {
+        assert_eq!(english().tokenize("Wait... Then he left.", true), vec!["Wait...", "Then he left."]);
+        assert_eq!(english().tokenize("Well... and then he left.", true), vec!["Well... and then he left."]);
+    }
+
+    #[test]
+    fn test_combined_terminators() {
+        assert_eq!(english().tokenize("Really?! I did not know.", true), vec!["Really?!", "I did not know."]);
+        assert_eq!(english().tokenize("Stop!!! He did not.", true), vec!["Stop!!!", "He did not."]);
+        assert_eq!(english().tokenize("What?.. I see.", true), vec!["What?..", "I see."]);
+    }
+
+    #[test]
+    fn test_unicode_terminators() {
+        assert_eq!(english().tokenize("No‼ Never.", true), vec!["No‼", "Never."]);
+        assert_eq!(english().tokenize("Why⁉ Because.", true), vec!["Why⁉", "Because."]);
+        assert_eq!(english().tokenize("What⁇ Yes⁈ Fine.", true), vec!["What⁇", "Yes⁈", "Fine."]);
+    }
+}
+
+#[cfg(test)]
+mod punkt_cyrillic_tests {
+
+    use crate::tokenize::profile::CaseFolding;
+    use crate::tokenize::punkt::PunktToken;
+
+    #[test]
+    fn test_cyrillic_case() {
+        let upper = PunktToken::new("Москва", false, false, CaseFolding::Default);
+        let lower = PunktToken::new("ёлка", false, false, CaseFolding::Default);
+
+        assert!(upper.first_upper());
+        assert!(lower.first_lower());
+        assert_eq!(upper.type_, "москва");
+    }
+}
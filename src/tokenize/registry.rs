@@ -0,0 +1,161 @@
+use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::tokenize::profile::LanguageProfile;
+use crate::tokenize::punkt::PunktSentenceTokenizer;
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+
+/// Holds the model of one language once it is loaded. Callers asking for a
+/// language that is being loaded wait on the slot instead of loading it too.
+type Slot = Arc<Mutex<Option<Arc<PunktSentenceTokenizer>>>>;
+
+lazy_static! {
+    static ref TOKENIZERS: Mutex<HashMap<Language, Slot>> = Mutex::new(HashMap::new());
+}
+
+/// The shared tokenizer of `language`, loading its weights on first use.
+/// Failed loads are not kept, the next call tries again.
+pub fn get_tokenizer(language: Language) -> Result<Arc<PunktSentenceTokenizer>, SentenceSplitterError> {
+    let slot = TOKENIZERS.lock().unwrap().entry(language).or_default().clone();
+    let mut tokenizer = slot.lock().unwrap();
+    if let Some(tokenizer) = tokenizer.as_ref() {
+        return Ok(tokenizer.clone());
+    }
+
+    let weights = PunktSentenceTokenizer::_load_weights(&PunktSentenceTokenizer::_language_weights(language)?)?;
+    let loaded = Arc::new(PunktSentenceTokenizer::from_weights(weights, &LanguageProfile::for_language(language)));
+    *tokenizer = Some(loaded.clone());
+    Ok(loaded)
+}
+
+/// Loads the models of `languages` ahead of their first use.
+pub fn preload(languages: &[Language]) -> Result<(), SentenceSplitterError> {
+    for language in languages {
+        get_tokenizer(*language)?;
+    }
+    Ok(())
+}
+
+/// Drops the registry's reference to the model of `language`; tokenizers
+/// handed out before stay usable. Returns false when it was not loaded.
+pub fn evict(language: Language) -> bool {
+    // not waiting for a load while holding the lock of every language
+    let slot = TOKENIZERS.lock().unwrap().remove(&language);
+    match slot {
+        Some(slot) => slot.lock().unwrap().is_some(),
+        None => false
+    }
+}
+
+pub fn evict_all() {
+    TOKENIZERS.lock().unwrap().clear();
+}
+
+/// Languages whose model is loaded, sorted by name. Languages that are
+/// being loaded are left out.
+pub fn loaded_languages() -> Vec<Language> {
+    let slots: Vec<(Language, Slot)> = TOKENIZERS.lock().unwrap()
+        .iter()
+        .map(|(language, slot)| (*language, slot.clone()))
+        .collect();
+    let mut languages: Vec<Language> = slots.into_iter()
+        .filter(|(_, slot)| matches!(slot.try_lock(), Ok(tokenizer) if tokenizer.is_some()))
+        .map(|(language, _)| language)
+        .collect();
+    languages.sort_by_key(|language| language.to_string());
+    languages
+}
+
+
+#[cfg(test)]
+mod tokenizer_registry_tests {
+    use super::*;
+    use crate::helper::languages::register_language;
+    use crate::helper::resources::{LocalResource, Resource};
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn register(name: &str, weights: &str) -> Language {
+        register_language(name, &name.to_lowercase(), LanguageProfile::new(), Resource::Local(LocalResource {
+            local_path: PathBuf::from(weights)
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_models_are_shared() {
+        let language = register("RegistryShared", "data/weights/English.json");
+
+        let handles: Vec<_> = (0..8).map(|_| thread::spawn(move || get_tokenizer(language).unwrap())).collect();
+        let tokenizers: Vec<Arc<PunktSentenceTokenizer>> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        for tokenizer in &tokenizers {
+            assert!(Arc::ptr_eq(tokenizer, &tokenizers[0]));
+        }
+        assert_eq!(tokenizers[0].tokenize("He left. She stayed.", true), vec!["He left.", "She stayed."]);
+    }
+
+    #[test]
+    fn test_preload_and_evict() {
+        let language = register("RegistryEvict", "data/weights/German.json");
+
+        preload(&[language]).unwrap();
+        assert!(loaded_languages().contains(&language));
+        let first = get_tokenizer(language).unwrap();
+
+        assert!(evict(language));
+        assert!(!evict(language));
+        assert!(!loaded_languages().contains(&language));
+
+        let second = get_tokenizer(language).unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(first.tokenize("Er kam. Sie ging.", true), second.tokenize("Er kam. Sie ging.", true));
+    }
+
+    #[test]
+    fn test_loading_languages_do_not_block_the_others() {
+        let loaded = register("RegistryLoaded", "data/weights/English.json");
+        let loading = register("RegistryLoading", "data/weights/English.json");
+        get_tokenizer(loaded).unwrap();
+
+        // a slot that stays locked, as if its weights were being downloaded
+        let slot = TOKENIZERS.lock().unwrap().entry(loading).or_default().clone();
+        let _loading = slot.lock().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let languages = loaded_languages();
+            sender.send((languages, evict(loaded))).unwrap();
+        });
+
+        let (languages, evicted) = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(languages.contains(&loaded));
+        assert!(!languages.contains(&loading));
+        assert!(evicted);
+    }
+
+    #[test]
+    fn test_failed_loads_are_retried() {
+        let language = register("RegistryMissing", "does/not/exist.json");
+
+        assert!(matches!(get_tokenizer(language), Err(SentenceSplitterError::IOError(_))));
+        assert!(!loaded_languages().contains(&language));
+
+        register("RegistryMissing", "data/weights/English.json");
+        assert!(get_tokenizer(language).is_ok());
+    }
+
+    #[test]
+    fn test_registering_again_evicts_the_model() {
+        let language = register("RegistryReplaced", "data/weights/English.json");
+        let first = get_tokenizer(language).unwrap();
+
+        register("RegistryReplaced", "data/weights/French.json");
+        let second = get_tokenizer(language).unwrap();
+
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+}
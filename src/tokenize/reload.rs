@@ -0,0 +1,236 @@
+use crate::helper::common::Language;
+use crate::helper::errors::SentenceSplitterError;
+use crate::helper::resources::{sha256_bytes, sha256_file, LocalResource, Resource};
+use crate::tokenize::profile::LanguageProfile;
+use crate::tokenize::punkt::{PunktParameters, PunktSentenceTokenizer};
+
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+
+/// A tokenizer whose weights can be replaced while it is in use. Calls to
+/// `tokenize` keep the model they started with, later calls get the new one.
+pub struct ReloadableTokenizer {
+    resource: Resource,
+    profile: LanguageProfile,
+    current: RwLock<Arc<PunktSentenceTokenizer>>,
+    /// SHA-256 of the weights of the last successful load.
+    loaded: Mutex<String>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl ReloadableTokenizer {
+
+    /// Loads the weights of `resource`, which must be valid.
+    pub fn new(resource: Resource, language: Option<Language>) -> Result<Self, SentenceSplitterError> {
+        let profile = match language {
+            Some(language) => LanguageProfile::for_language(language),
+            None => LanguageProfile::default()
+        };
+        let content = resource.read()?;
+        let tokenizer = PunktSentenceTokenizer::from_weights(_valid_weights(&content)?, &profile);
+        let loaded = sha256_bytes(&content);
+
+        Ok(ReloadableTokenizer {
+            resource,
+            profile,
+            current: RwLock::new(Arc::new(tokenizer)),
+            loaded: Mutex::new(loaded),
+            last_error: Mutex::new(None),
+        })
+    }
+
+    pub fn from_path(path: &Path, language: Option<Language>) -> Result<Self, SentenceSplitterError> {
+        Self::new(Resource::Local(LocalResource { local_path: path.to_path_buf() }), language)
+    }
+
+    /// The current model.
+    pub fn tokenizer(&self) -> Arc<PunktSentenceTokenizer> {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn tokenize(&self, text: &str, realign_boundaries: bool) -> Vec<String> {
+        self.tokenizer().tokenize(text, realign_boundaries)
+    }
+
+    /// Reads the weights again and swaps them in. When they cannot be read
+    /// or are invalid the current model is kept and the error returned.
+    pub fn reload(&self) -> Result<(), SentenceSplitterError> {
+        let content = match self.resource.read() {
+            Ok(content) => content,
+            Err(error) => return Err(self._failed(error))
+        };
+        let weights = match _valid_weights(&content) {
+            Ok(weights) => weights,
+            Err(error) => return Err(self._failed(error))
+        };
+        let tokenizer = Arc::new(PunktSentenceTokenizer::from_weights(weights, &self.profile));
+
+        *self.current.write().unwrap() = tokenizer;
+        *self.loaded.lock().unwrap() = sha256_bytes(&content);
+        *self.last_error.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Reloads when the content of the weight file differs from the last
+    /// successful load, whatever its modification time says. Returns whether
+    /// a new model was swapped in. Resources that are not local files never
+    /// change.
+    pub fn reload_if_modified(&self) -> Result<bool, SentenceSplitterError> {
+        let path = match &self.resource {
+            Resource::Local(resource) => &resource.local_path,
+            _ => return Ok(false)
+        };
+        let digest = match sha256_file(path) {
+            Ok(digest) => digest,
+            Err(error) => return Err(self._failed(error))
+        };
+        if digest == *self.loaded.lock().unwrap() {
+            // e.g. a broken file was put back, the model matches it again
+            *self.last_error.lock().unwrap() = None;
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    fn _failed(&self, error: SentenceSplitterError) -> SentenceSplitterError {
+        *self.last_error.lock().unwrap() = Some(error.to_string());
+        error
+    }
+
+    /// Error of the last failed reload, cleared by the next successful one.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Checks the weight file every `interval` on a background thread, until
+    /// the returned watcher is dropped. Failed reloads are retried on the
+    /// next check and reported by `last_error`.
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> ReloadWatcher {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let tokenizer = self.clone();
+
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let _ = tokenizer.reload_if_modified();
+            }
+        });
+
+        ReloadWatcher { stop: Some(stop), thread: Some(thread) }
+    }
+}
+
+/// Stops watching the weight file when dropped.
+pub struct ReloadWatcher {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for ReloadWatcher {
+    fn drop(&mut self) {
+        // disconnecting the channel ends the loop
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Parses the weights and rejects files that cannot come from training, such
+/// as one truncated to its empty sets.
+fn _valid_weights(content: &[u8]) -> Result<PunktParameters, SentenceSplitterError> {
+    let weights: PunktParameters = serde_json::from_slice(content)?;
+    if weights.ortho_context.is_empty() {
+        return Err(SentenceSplitterError::WeightFileError(String::from("the weights have no orthographic context")));
+    }
+    Ok(weights)
+}
+
+
+#[cfg(test)]
+mod reloadable_tokenizer_tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    static TEXT: &str = "Siehe Kapitel 3, vgl. Abschnitt 2. Sie blieb.";
+
+    fn weight_file(name: &str, weights: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("sentence-splitter-{}-{}.json", std::process::id(), name));
+        fs::copy(weights, &path).unwrap();
+        path
+    }
+
+    /// Rewrites `path` keeping its modification time, as coarse file
+    /// systems do for writes in quick succession.
+    fn replace(path: &Path, content: &[u8]) {
+        let modified = fs::metadata(path).unwrap().modified().unwrap();
+        fs::write(path, content).unwrap();
+        fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn test_reload() {
+        let path = weight_file("reload", "data/weights/English.json");
+        let tokenizer = ReloadableTokenizer::from_path(&path, Some(Language::German)).unwrap();
+        let english = tokenizer.tokenizer();
+        assert_eq!(tokenizer.tokenize(TEXT, true), vec!["Siehe Kapitel 3, vgl.", "Abschnitt 2.", "Sie blieb."]);
+
+        assert!(!tokenizer.reload_if_modified().unwrap());
+        replace(&path, &fs::read("data/weights/German.json").unwrap());
+        assert!(tokenizer.reload_if_modified().unwrap());
+
+        assert_eq!(tokenizer.tokenize(TEXT, true), vec!["Siehe Kapitel 3, vgl. Abschnitt 2.", "Sie blieb."]);
+        // handed out before the reload
+        assert_eq!(english.tokenize(TEXT, true), vec!["Siehe Kapitel 3, vgl.", "Abschnitt 2.", "Sie blieb."]);
+    }
+
+    #[test]
+    fn test_invalid_weights_keep_the_model() {
+        let path = weight_file("invalid", "data/weights/German.json");
+        let tokenizer = ReloadableTokenizer::from_path(&path, Some(Language::German)).unwrap();
+        let german = tokenizer.tokenizer();
+
+        replace(&path, b"{\"abbrev_types\": [");
+        assert!(matches!(tokenizer.reload_if_modified(), Err(SentenceSplitterError::WeightFileError(_))));
+        assert!(tokenizer.last_error().is_some());
+
+        replace(&path, b"{\"abbrev_types\": [], \"collocations\": [], \"sent_starters\": [], \"ortho_context\": {}}");
+        assert!(matches!(tokenizer.reload(), Err(SentenceSplitterError::WeightFileError(_))));
+
+        assert!(Arc::ptr_eq(&tokenizer.tokenizer(), &german));
+
+        // the weights of the current model, nothing to reload
+        replace(&path, &fs::read("data/weights/German.json").unwrap());
+        assert!(!tokenizer.reload_if_modified().unwrap());
+        assert!(tokenizer.last_error().is_none());
+        assert!(Arc::ptr_eq(&tokenizer.tokenizer(), &german));
+    }
+
+    #[test]
+    fn test_watch() {
+        let path = weight_file("watch", "data/weights/English.json");
+        let tokenizer = Arc::new(ReloadableTokenizer::from_path(&path, Some(Language::German)).unwrap());
+        let watcher = tokenizer.watch(Duration::from_millis(10));
+
+        replace(&path, &fs::read("data/weights/German.json").unwrap());
+        let mut attempts = 0;
+        while tokenizer.tokenize(TEXT, true).len() != 2 && attempts < 500 {
+            thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        drop(watcher);
+
+        assert_eq!(tokenizer.tokenize(TEXT, true), vec!["Siehe Kapitel 3, vgl. Abschnitt 2.", "Sie blieb."]);
+        assert_eq!(Arc::strong_count(&tokenizer), 1);
+    }
+
+    #[test]
+    fn test_invalid_initial_weights() {
+        assert!(ReloadableTokenizer::new(Resource::Static(b"{}"), None).is_err());
+    }
+}
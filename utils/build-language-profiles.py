@@ -0,0 +1,92 @@
+import os
+import re
+import json
+import glob
+import argparse
+from collections import Counter
+
+PROFILE_SIZE = 1000
+
+# Frequent function words, which the vocabulary of the weight files does not
+# rank above rare words. They settle short texts.
+COMMON_WORDS = {
+    "Czech": "a v se na je že to s z do jako o ale by jsem jsou byl bylo pro nebo už",
+    "Danish": "og i at det en den er til på ikke med af for de som har jeg han hun vi var et så men fra mig",
+    "Dutch": "de het een en van in is op te dat die niet met voor zijn er aan ook als bij ik heb hij zij we was geen tot",
+    "English": "the of and to in is was that it for with as he she at by from his her have has had not are were be this which or an but they you we all will would there their what so if about who can more when after",
+    "Estonian": "ja on ei see et ta oli kui ka aga või mis ma sa me te nad seda oma veel",
+    "Finnish": "ja on ei se että hän oli ovat mutta kun niin myös tai minä sinä me te he tämä joka olla",
+    "French": "le la les de des du et est en un une que qui dans pour pas ne au aux il elle je nous vous ils sur avec se son sa ses ce cette mais ou par plus était sont",
+    "German": "der die das und ist nicht ein eine zu den von mit sich des auf für im dem es sie er ich wir ihr war sind wird auch als an bei nach aus wie aber oder noch nur so hat haben kein keine",
+    "Greek": "και το η ο να του της σε με τα για δεν από που οι τον την στο",
+    "Italian": "il lo la i gli le di del della e è in un una che per con non si al alla sono ho ha come ma più anche ci",
+    "Norwegian": "og i er det en den til på ikke med av for de som har jeg han hun vi var et så men fra meg ikkje eg",
+    "Polish": "i w nie na z się to że do jest jak o ale po co tak za od przez są był była dla",
+    "Portuguese": "o a os as de do da dos das e em um uma que é para com não se no na ao por mais foi são muito até",
+    "Slovenian": "in je v na se da so za z pa ki ne bi tudi kot to ali iz sem smo bil",
+    "Spanish": "el la los las de del y en un una que es por para con no se su sus al lo como más pero fue son está yo muy",
+    "Swedish": "och i att det en den är till på inte med av för de som har jag han hon vi var ett så men från ingen",
+    "Turkish": "ve bir bu da de için ile ne çok daha o gibi ama var yok ben sen biz",
+}
+
+
+def ngrams(word: str):
+    word = f" {word} "
+    for n in (1, 2, 3):
+        for i in range(len(word) - n + 1):
+            gram = word[i:i + n]
+            if gram.strip():
+                yield gram
+
+
+def build_profile(weight_file: str):
+    with open(weight_file) as fp:
+        vocabulary = json.load(fp)["ortho_context"]
+
+    counts = Counter()
+    for word in vocabulary:
+        if re.fullmatch(r"[^\W\d_]+", word):
+            counts.update(ngrams(word))
+
+    ranked = sorted(counts.items(), key=lambda item: (-item[1], item[0]))
+    return [gram for gram, _ in ranked[:PROFILE_SIZE]]
+
+
+def save_profiles(profiles: dict, output: str):
+    # one language per line
+    lines = [
+        f"{json.dumps(language)}: {json.dumps(profile, ensure_ascii=False)}"
+        for language, profile in sorted(profiles.items())
+    ]
+    with open(output, 'w') as fp:
+        fp.write("{\n" + ",\n".join(lines) + "\n}\n")
+
+
+def main(weights: str, output: str):
+    profiles = {}
+    for weight_file in sorted(glob.glob(os.path.join(weights, "*.json"))):
+        language = os.path.basename(weight_file)[:-len(".json")]
+        profiles[language] = {
+            "ngrams": build_profile(weight_file),
+            "words": COMMON_WORDS.get(language, "").split(),
+        }
+    save_profiles(profiles, output)
+
+    print(f"Language profiles are created at `{output}`")
+
+
+if __name__ == '__main__':
+    parser = argparse.ArgumentParser(
+        description='Builds the character n-gram profiles used for language detection from the vocabulary of the weight files.'
+    )
+    parser.add_argument(
+        '-weights',
+        default=os.path.join('data', 'weights'),
+        help='Directory with the weight files.'
+    )
+    parser.add_argument(
+        '-output',
+        default=os.path.join('data', 'language-profiles.json'),
+        help='Path of the profile file to write.'
+    )
+    main(**vars(parser.parse_args()))
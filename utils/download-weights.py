@@ -14,9 +14,11 @@ AVAILABLE_LANGUAGES = {
     "german",
     "greek",
     "italian",
+    "malayalam",
     "norwegian",
     "polish",
     "portuguese",
+    "russian",
     "slovene",
     "spanish",
     "swedish",